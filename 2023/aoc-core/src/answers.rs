@@ -0,0 +1,106 @@
+use std::{collections::BTreeMap, fs, path::Path, str::FromStr};
+
+use toml::{Table, Value};
+
+use crate::{Error, Result};
+
+/// Expected answers keyed by year, day and part, read from TOML like
+///
+/// ```toml
+/// [2023.day03]
+/// part1 = 4361
+/// part2 = "467835"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Answers(BTreeMap<(u32, u32, u8), String>);
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let s = fs::read_to_string(path)
+            .map_err(|e| Error::Answers(format!("{}: {}", path.display(), e)))?;
+        s.parse()
+    }
+
+    pub fn get(&self, year: u32, day: u32, part: u8) -> Option<&str> {
+        self.0.get(&(year, day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, year: u32, day: u32, part: u8, answer: String) {
+        self.0.insert((year, day, part), answer);
+    }
+
+    fn table<'a>(value: &'a Value, key: &str) -> Result<&'a Table> {
+        value
+            .as_table()
+            .ok_or_else(|| Error::Answers(format!("`{}` should be a table", key)))
+    }
+
+    fn key_number<T: FromStr>(key: &str, prefix: &str) -> Result<T> {
+        key.strip_prefix(prefix)
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| Error::Answers(format!("expected `{}N`, found `{}`", prefix, key)))
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let years: Table = s.parse().map_err(|e| Error::Answers(format!("{}", e)))?;
+        let mut answers = Self::default();
+
+        for (year_key, days) in &years {
+            let year = Self::key_number(year_key, "")?;
+            for (day_key, parts) in Self::table(days, year_key)? {
+                let day = Self::key_number(day_key, "day")?;
+                for (part_key, answer) in Self::table(parts, day_key)? {
+                    let part = Self::key_number(part_key, "part")?;
+                    let answer = match answer {
+                        Value::String(s) => s.clone(),
+                        Value::Integer(n) => n.to_string(),
+                        _ => {
+                            return Err(Error::Answers(format!(
+                                "answer for {}.{}.{} should be a string or integer",
+                                year_key, day_key, part_key
+                            )))
+                        }
+                    };
+                    answers.insert(year, day, part, answer);
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let answers: Answers = "[2023.day03]
+part1 = 4361
+part2 = \"467835\"
+
+[2023.day04]
+part1 = 13
+"
+        .parse()
+        .unwrap();
+
+        assert_eq!(answers.get(2023, 3, 1), Some("4361"));
+        assert_eq!(answers.get(2023, 3, 2), Some("467835"));
+        assert_eq!(answers.get(2023, 4, 1), Some("13"));
+        assert_eq!(answers.get(2023, 4, 2), None);
+    }
+
+    #[test]
+    fn invalid_keys() {
+        assert!("[2023.three]\npart1 = 1".parse::<Answers>().is_err());
+        assert!("[2023.day03]\nfirst = 1".parse::<Answers>().is_err());
+        assert!("[2023.day03]\npart1 = 1.5".parse::<Answers>().is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt;
+
+use crate::{Result, Solution};
+
+/// A parsed [`Solution`] with its answer types erased, so days with
+/// different answers can be run side by side.
+pub trait DynSolution {
+    fn part1(&self) -> Result<String>;
+
+    fn part2(&self) -> Result<String>;
+}
+
+impl<S: Solution> DynSolution for S {
+    fn part1(&self) -> Result<String> {
+        Ok(Solution::part1(self)?.to_string())
+    }
+
+    fn part2(&self) -> Result<String> {
+        Ok(Solution::part2(self)?.to_string())
+    }
+}
+
+/// Registry entry for a day, built from its [`Solution`] with [`Day::new`].
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u32,
+    parse: fn(&str) -> Result<Box<dyn DynSolution>>,
+}
+
+impl Day {
+    pub const fn new<S: Solution + 'static>() -> Self {
+        Self {
+            number: S::DAY,
+            parse: parse_boxed::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn DynSolution>> {
+        (self.parse)(input)
+    }
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Day").field("number", &self.number).finish()
+    }
+}
+
+fn parse_boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn DynSolution>> {
+    Ok(Box::new(S::parse(input)?))
+}
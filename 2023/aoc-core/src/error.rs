@@ -0,0 +1,35 @@
+use std::io;
+
+use crate::{input::Source, ParseError};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("day {day}: failed reading input from {input}")]
+    Input {
+        day: u32,
+        input: Source,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    #[error("part {0} has no answer")]
+    NoAnswer(u8),
+
+    #[error("part {part} failed")]
+    Solve {
+        part: u8,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("{0}")]
+    Usage(String),
+
+    #[error("invalid answers file: {0}")]
+    Answers(String),
+}
@@ -0,0 +1,112 @@
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, Result};
+
+/// Directory holding `dayNN/input.txt` files, overriding the default path.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where a day's input is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Picks the input for `day`: `arg` when given (`-` meaning stdin),
+    /// then `$AOC_INPUT_DIR/dayNN/input.txt`, then `default`.
+    pub fn resolve(day: u32, arg: Option<&str>, default: impl AsRef<Path>) -> Self {
+        let dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+        Self::resolve_from(day, arg, dir, default.as_ref())
+    }
+
+    fn resolve_from(day: u32, arg: Option<&str>, dir: Option<PathBuf>, default: &Path) -> Self {
+        match (arg, dir) {
+            (Some("-"), _) => Self::Stdin,
+            (Some(path), _) => Self::File(PathBuf::from(path)),
+            (None, Some(dir)) => Self::in_dir(dir, day),
+            (None, None) => Self::File(default.to_path_buf()),
+        }
+    }
+
+    /// The `dayNN/input.txt` file under `dir`.
+    pub fn in_dir(dir: impl AsRef<Path>, day: u32) -> Self {
+        Self::File(dir.as_ref().join(day_path(day)))
+    }
+
+    /// Reads the whole input, naming the day and source on failure.
+    pub fn read(&self, day: u32) -> Result<String> {
+        let read = match self {
+            Self::Stdin => {
+                let mut s = String::new();
+                io::stdin().read_to_string(&mut s).map(|_| s)
+            }
+            Self::File(path) => fs::read_to_string(path),
+        };
+        read.map_err(|source| Error::Input {
+            day,
+            input: self.clone(),
+            source,
+        })
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "stdin"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Path of a day's input relative to the workspace root.
+pub fn day_path(day: u32) -> PathBuf {
+    PathBuf::from(format!("day{:02}", day)).join("input.txt")
+}
+
+/// Reads a day's whole input file, naming the day and path on failure.
+pub fn read(day: u32, path: impl AsRef<Path>) -> Result<String> {
+    Source::File(path.as_ref().to_path_buf()).read(day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        let default = Path::new("input.txt");
+        let dir = Some(PathBuf::from("/inputs"));
+
+        assert_eq!(
+            Source::resolve_from(3, Some("-"), dir.clone(), default),
+            Source::Stdin
+        );
+        assert_eq!(
+            Source::resolve_from(3, Some("other.txt"), dir.clone(), default),
+            Source::File(PathBuf::from("other.txt"))
+        );
+        assert_eq!(
+            Source::resolve_from(3, None, dir, default),
+            Source::File(PathBuf::from("/inputs/day03/input.txt"))
+        );
+        assert_eq!(
+            Source::resolve_from(3, None, None, default),
+            Source::File(PathBuf::from("input.txt"))
+        );
+    }
+
+    #[test]
+    fn missing_file_names_day_and_path() {
+        let e = read(4, "does/not/exist.txt").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 4: failed reading input from does/not/exist.txt"
+        );
+    }
+}
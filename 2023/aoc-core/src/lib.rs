@@ -0,0 +1,56 @@
+//! Shared pieces for every day of the 2023 workspace: the [`Solution`]
+//! trait each day implements, a common [`Error`], input loading and text
+//! tables for output.
+
+use std::fmt::Display;
+
+pub use answers::Answers;
+pub use day::{Day, DynSolution};
+pub use error::{Error, Result};
+pub use parse_error::ParseError;
+pub use table::Table;
+
+mod answers;
+mod day;
+mod error;
+pub mod input;
+pub mod parse;
+mod parse_error;
+mod table;
+
+/// Puzzle year of every day in this workspace.
+pub const YEAR: u32 = 2023;
+
+/// A day's puzzle, parsed once and solved for both parts.
+pub trait Solution: Sized {
+    const DAY: u32;
+
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self>;
+
+    fn part1(&self) -> Result<Self::Answer1>;
+
+    fn part2(&self) -> Result<Self::Answer2>;
+}
+
+/// Entry point of a day binary, taking an optional input path argument
+/// (`-` for stdin, see [`input::Source::resolve`]) and printing both answers.
+pub fn run<S: Solution>() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let arg = args.next();
+    if let Some(extra) = args.next() {
+        return Err(Error::Usage(format!(
+            "unexpected argument `{}`, usage: day{:02} [INPUT | -]",
+            extra,
+            S::DAY
+        )));
+    }
+
+    let input = input::Source::resolve(S::DAY, arg.as_deref(), "input.txt").read(S::DAY)?;
+    let solution = S::parse(&input)?;
+    println!("Day {:02} part 1: {}", S::DAY, solution.part1()?);
+    println!("Day {:02} part 2: {}", S::DAY, solution.part2()?);
+    Ok(())
+}
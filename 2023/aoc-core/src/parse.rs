@@ -0,0 +1,298 @@
+//! Small parser combinators for the puzzles' line based formats.
+//!
+//! A [`Parser`] is any function taking an [`Input`] and returning the rest
+//! of it with what was parsed, or a [`Failure`] at the position it could not
+//! go past. Combinators backtrack unless the failure is [`cut`], and
+//! [`parse_line`] turns it into a [`ParseError`] pointing at the offending
+//! token.
+
+use std::{borrow::Cow, str::FromStr};
+
+use crate::ParseError;
+
+/// A line being parsed, and how far into it the parser got.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Input<'a> {
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Self { line, pos: 0 }
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    fn advance(self, len: usize) -> Self {
+        Self {
+            line: self.line,
+            pos: self.pos + len,
+        }
+    }
+
+    /// Fails here, expecting `expected`.
+    pub fn fail<T>(&self, expected: impl Into<Cow<'static, str>>) -> PResult<'a, T> {
+        Err(Failure {
+            pos: self.pos,
+            expected: expected.into(),
+            cut: false,
+        })
+    }
+}
+
+/// What a parser expected at the byte `pos` of the line. A `cut` failure
+/// is final: no alternative is tried and repetitions do not stop at it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub pos: usize,
+    pub expected: Cow<'static, str>,
+    pub cut: bool,
+}
+
+impl Failure {
+    /// Keeps whichever failure got further, merging equally far ones.
+    fn furthest(self, other: Self) -> Self {
+        match self.pos.cmp(&other.pos) {
+            std::cmp::Ordering::Greater => self,
+            std::cmp::Ordering::Less => other,
+            std::cmp::Ordering::Equal => Self {
+                pos: self.pos,
+                expected: format!("{} or {}", self.expected, other.expected).into(),
+                cut: self.cut || other.cut,
+            },
+        }
+    }
+
+    /// Error pointing at the whitespace separated token at the failure.
+    pub fn into_error(self, day: u32, line: &str) -> ParseError {
+        let token = line[self.pos..]
+            .find(' ')
+            .unwrap_or(line.len() - self.pos)
+            .max(1);
+        ParseError::new(day, line, self.pos, token, self.expected)
+    }
+}
+
+pub type PResult<'a, T> = Result<(Input<'a>, T), Failure>;
+
+pub trait Parser<'a, T>: Fn(Input<'a>) -> PResult<'a, T> {}
+
+impl<'a, T, F: Fn(Input<'a>) -> PResult<'a, T>> Parser<'a, T> for F {}
+
+/// Runs `parser` over the whole `line`, failing if anything is left over.
+pub fn parse_line<'a, T>(
+    day: u32,
+    line: &'a str,
+    parser: impl Parser<'a, T>,
+) -> Result<T, ParseError> {
+    terminated(parser, eol())(Input::new(line))
+        .map(|(_, value)| value)
+        .map_err(|f| f.into_error(day, line))
+}
+
+/// The literal `tag`.
+pub fn tag<'a>(tag: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| match input.rest().starts_with(tag) {
+        true => Ok((input.advance(tag.len()), &input.rest()[..tag.len()])),
+        false => input.fail(format!("`{}`", tag)),
+    }
+}
+
+/// A non-empty run of characters matching `pred`.
+pub fn take_while1<'a>(
+    pred: impl Fn(char) -> bool,
+    expected: &'static str,
+) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        match len {
+            0 => input.fail(expected),
+            len => Ok((input.advance(len), &rest[..len])),
+        }
+    }
+}
+
+/// A run of ASCII digits, parsed into `T`.
+pub fn number<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let (rest, digits) = take_while1(|c| c.is_ascii_digit(), "a number")(input)?;
+        match digits.parse() {
+            Ok(n) => Ok((rest, n)),
+            Err(_) => cut(|input: Input<'a>| input.fail("a number that fits"))(input),
+        }
+    }
+}
+
+/// Any number of spaces.
+pub fn space0<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| {
+        let len = input.rest().len() - input.rest().trim_start_matches(' ').len();
+        Ok((input.advance(len), ()))
+    }
+}
+
+/// At least one space.
+pub fn space1<'a>() -> impl Parser<'a, ()> {
+    map(take_while1(|c| c == ' ', "a space"), |_| ())
+}
+
+/// The end of the line.
+pub fn eol<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| match input.rest().is_empty() {
+        true => Ok((input, ())),
+        false => input.fail("end of line"),
+    }
+}
+
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input| parser(input).map(|(rest, value)| (rest, f(value)))
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input| {
+        let (input, a) = first(input)?;
+        let (input, b) = second(input)?;
+        Ok((input, (a, b)))
+    }
+}
+
+pub fn preceded<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+pub fn terminated<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+pub fn delimited<'a, A, B, C>(
+    open: impl Parser<'a, A>,
+    parser: impl Parser<'a, B>,
+    close: impl Parser<'a, C>,
+) -> impl Parser<'a, B> {
+    preceded(open, terminated(parser, close))
+}
+
+/// Makes every failure of `parser` final.
+pub fn cut<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input| parser(input).map_err(|f| Failure { cut: true, ..f })
+}
+
+/// `first`, or `second` when it fails. If both fail, the furthest failure wins.
+pub fn alt<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |input| match first(input) {
+        Err(f1) if !f1.cut => second(input).map_err(|f2| f1.furthest(f2)),
+        result => result,
+    }
+}
+
+/// `parser` repeated until it fails or stops making progress.
+pub fn many0<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: Input<'a>| {
+        let mut values = Vec::new();
+        loop {
+            match parser(input) {
+                Ok((rest, value)) if rest.pos > input.pos => {
+                    values.push(value);
+                    input = rest;
+                }
+                Err(f) if f.cut => return Err(f),
+                _ => return Ok((input, values)),
+            }
+        }
+    }
+}
+
+/// One or more `parser`, separated by `sep`. Once a separator matched,
+/// the item after it must parse too, so its failure is reported as is.
+pub fn separated1<'a, T, S>(
+    parser: impl Parser<'a, T>,
+    sep: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input| {
+        let (mut input, first) = parser(input)?;
+        let mut values = vec![first];
+        while let Ok((rest, _)) = sep(input) {
+            let (rest, value) = cut(&parser)(rest)?;
+            values.push(value);
+            input = rest;
+        }
+        Ok((input, values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers<'a>() -> impl Parser<'a, Vec<u64>> {
+        preceded(
+            pair(tag("seeds:"), space0()),
+            separated1(number(), space1()),
+        )
+    }
+
+    #[test]
+    fn parses() {
+        assert_eq!(
+            parse_line(0, "seeds: 1 22  333", numbers()),
+            Ok(vec![1, 22, 333])
+        );
+        let colour = alt(tag("red"), tag("blue"));
+        assert_eq!(parse_line(0, "blue", colour), Ok("blue"));
+    }
+
+    #[test]
+    fn points_at_failing_token() {
+        let e = parse_line(5, "seeds: 1 2x 3", numbers()).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 5, line 1, column 11: expected end of line, found `x`"
+        );
+
+        let e = parse_line(5, "seed: 1", numbers()).unwrap_err();
+        assert_eq!((e.column, e.snippet.as_str()), (1, "seed:"));
+
+        let colour = preceded(
+            pair(number::<u64>(), space1()),
+            alt(tag("red"), tag("blue")),
+        );
+        let e = parse_line(2, "3 green", colour).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 2, line 1, column 3: expected `red` or `blue`, found `green`"
+        );
+    }
+
+    #[test]
+    fn separated_items_must_parse() {
+        let e = parse_line(5, "seeds: 1 2 x", numbers()).unwrap_err();
+        assert_eq!((e.column, e.expected.as_str()), (12, "a number"));
+    }
+
+    #[test]
+    fn number_overflow() {
+        let e = parse_line(4, "99999999999999999999", number::<u64>()).unwrap_err();
+        assert_eq!((e.column, e.expected.as_str()), (1, "a number that fits"));
+
+        let numbers = many0(preceded(space1(), number::<u64>()));
+        let e = parse_line(4, " 1 99999999999999999999", numbers).unwrap_err();
+        assert_eq!((e.column, e.expected.as_str()), (4, "a number that fits"));
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+/// Where and why a day's input failed to parse.
+///
+/// `line` and `column` are 1-based, columns counting bytes. Parsers of a
+/// single line build it with [`ParseError::new`] and leave placing it in
+/// the whole input to their callers, through [`ParseError::line`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error at the `len` bytes starting at byte `column` of `line`.
+    pub fn new(
+        day: u32,
+        line: &str,
+        column: usize,
+        len: usize,
+        expected: impl Into<String>,
+    ) -> Self {
+        let column = column.min(line.len());
+        let end = (column + len).min(line.len());
+        Self {
+            day,
+            line: 1,
+            column: column + 1,
+            snippet: line.get(column..end).unwrap_or_default().to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Places the error on the 1-based `line` of the input.
+    pub fn line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found ",
+            self.day, self.line, self.column, self.expected
+        )?;
+        match self.snippet.is_empty() {
+            true => write!(f, "end of line"),
+            false => write!(f, "`{}`", self.snippet),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let e = ParseError::new(2, "Game x: 1 red", 5, 1, "a number");
+        assert_eq!(
+            e.to_string(),
+            "day 2, line 1, column 6: expected a number, found `x`"
+        );
+        let e = e.line(3);
+        assert_eq!((e.line, e.column), (3, 6));
+    }
+}
@@ -0,0 +1,112 @@
+use std::{collections::BTreeMap, fs, path::Path, time::Instant};
+
+use anyhow::Context;
+use aoc_core::{input::Source, Day};
+use serde::{Deserialize, Serialize};
+
+/// Median nanoseconds of each stage of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub parse: u64,
+    pub part1: u64,
+    pub part2: u64,
+}
+
+impl Timings {
+    pub const STAGES: [&'static str; 3] = ["parse", "part1", "part2"];
+
+    pub fn stages(&self) -> [u64; 3] {
+        [self.parse, self.part1, self.part2]
+    }
+
+    /// Runs every stage of `day` `iterations` times, keeping the medians.
+    pub fn measure(day: &Day, source: &Source, iterations: usize) -> aoc_core::Result<Self> {
+        let input = source.read(day.number)?;
+        let (mut parse, mut part1, mut part2) = (Vec::new(), Vec::new(), Vec::new());
+
+        for _ in 0..iterations.max(1) {
+            let start = Instant::now();
+            let solution = day.parse(&input)?;
+            parse.push(start.elapsed().as_nanos() as u64);
+
+            let start = Instant::now();
+            solution.part1()?;
+            part1.push(start.elapsed().as_nanos() as u64);
+
+            let start = Instant::now();
+            solution.part2()?;
+            part2.push(start.elapsed().as_nanos() as u64);
+        }
+
+        Ok(Self {
+            parse: median(parse),
+            part1: median(part1),
+            part2: median(part2),
+        })
+    }
+}
+
+fn median(mut samples: Vec<u64>) -> u64 {
+    samples.sort_unstable();
+    samples[samples.len() / 2]
+}
+
+/// Timings of every benchmarked day, stored as JSON to compare later runs against.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub days: BTreeMap<u32, Timings>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let s = fs::read_to_string(path)
+            .with_context(|| format!("Failed reading baseline {}", path.display()))?;
+        serde_json::from_str(&s)
+            .with_context(|| format!("Failed parsing baseline {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let s = serde_json::to_string_pretty(self)?;
+        fs::write(path, s + "\n")
+            .with_context(|| format!("Failed writing baseline {}", path.display()))
+    }
+}
+
+/// Relative change from `baseline` to `current`, in percent.
+pub fn change(baseline: u64, current: u64) -> f64 {
+    (current as f64 - baseline as f64) / (baseline.max(1) as f64) * 100.0
+}
+
+/// Whether `current` is more than `threshold` percent slower than `baseline`.
+pub fn is_regression(baseline: u64, current: u64, threshold: f64) -> bool {
+    change(baseline, current) > threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regression() {
+        assert!(!is_regression(100, 105, 10.0));
+        assert!(!is_regression(100, 50, 10.0));
+        assert!(is_regression(100, 111, 10.0));
+        assert!(is_regression(0, 5, 10.0));
+    }
+
+    #[test]
+    fn baseline_json() {
+        let mut baseline = Baseline::default();
+        baseline.days.insert(
+            3,
+            Timings {
+                parse: 1,
+                part1: 20,
+                part2: 300,
+            },
+        );
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(json, r#"{"days":{"3":{"parse":1,"part1":20,"part2":300}}}"#);
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    cell::Cell,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/arcstur/advents-of-code by arcstur";
+
+/// Transport used to download inputs, so tests can swap the network out.
+pub trait Http {
+    fn get(&self, url: &str, session: &str) -> anyhow::Result<String>;
+}
+
+/// Default [`Http`] backend.
+#[derive(Debug)]
+pub struct Ureq(ureq::Agent);
+
+impl Default for Ureq {
+    fn default() -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Self(agent)
+    }
+}
+
+impl Http for Ureq {
+    fn get(&self, url: &str, session: &str) -> anyhow::Result<String> {
+        let body = self
+            .0
+            .get(url)
+            .header("Cookie", &format!("session={}", session))
+            .header("User-Agent", USER_AGENT)
+            .call()?
+            .body_mut()
+            .read_to_string()?;
+        Ok(body)
+    }
+}
+
+/// Downloads puzzle inputs into `{cache_dir}/{year}/dayNN/input.txt`,
+/// never fetching one that is already cached, and waiting at least
+/// `min_interval` between requests.
+#[derive(Debug)]
+pub struct Fetcher<H: Http> {
+    http: H,
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl<H: Http> Fetcher<H> {
+    pub fn new(http: H, session: String, cache_dir: PathBuf) -> Self {
+        Self {
+            http,
+            base_url: BASE_URL.to_string(),
+            session,
+            cache_dir,
+            min_interval: Duration::from_secs(3),
+            last_request: Cell::new(None),
+        }
+    }
+
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    pub fn cache_path(&self, year: u32, day: u32) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(aoc_core::input::day_path(day))
+    }
+
+    /// Path of the cached input, downloading it first when missing.
+    pub fn input(&self, year: u32, day: u32) -> anyhow::Result<PathBuf> {
+        let path = self.cache_path(year, day);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        self.wait();
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let input = self
+            .http
+            .get(&url, &self.session)
+            .with_context(|| format!("Failed fetching {}", url))?;
+        Self::write(&path, &input)?;
+        Ok(path)
+    }
+
+    fn wait(&self) {
+        if let Some(last) = self.last_request.get() {
+            if let Some(remaining) = self.min_interval.checked_sub(last.elapsed()) {
+                thread::sleep(remaining);
+            }
+        }
+        self.last_request.set(Some(Instant::now()));
+    }
+
+    /// Writes through a temporary file, so an interrupted download
+    /// never leaves a partial input in the cache.
+    fn write(path: &Path, input: &str) -> anyhow::Result<()> {
+        let dir = path.parent().expect("Cache path has a parent");
+        fs::create_dir_all(dir).with_context(|| format!("Failed creating {}", dir.display()))?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, input).with_context(|| format!("Failed writing {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed writing {}", path.display()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+
+    /// Serves `body` to every request on a local port, recording the
+    /// lowercased request line and cookie of each.
+    fn stub_server(body: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = Vec::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        break;
+                    }
+                    let line = line.to_lowercase();
+                    if !line.contains(':') || line.starts_with("cookie:") {
+                        request.push(line);
+                    }
+                }
+                recorded.lock().unwrap().push(request.join("\n"));
+
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+
+    #[test]
+    fn fetches_once_and_caches() {
+        let (url, requests) = stub_server("1abc2\n");
+        let cache = tempfile::tempdir().unwrap();
+        let fetcher = Fetcher::new(Ureq::default(), "secret".to_string(), cache.path().into())
+            .base_url(&url)
+            .min_interval(Duration::ZERO);
+
+        let path = fetcher.input(2023, 1).unwrap();
+        assert_eq!(path, cache.path().join("2023/day01/input.txt"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1abc2\n");
+
+        fetcher.input(2023, 1).unwrap();
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec!["get /2023/day/1/input http/1.1\ncookie: session=secret"]
+        );
+    }
+
+    #[test]
+    fn waits_between_requests() {
+        let (url, requests) = stub_server("input");
+        let cache = tempfile::tempdir().unwrap();
+        let fetcher = Fetcher::new(Ureq::default(), "secret".to_string(), cache.path().into())
+            .base_url(&url)
+            .min_interval(Duration::from_millis(200));
+
+        let start = Instant::now();
+        fetcher.input(2023, 1).unwrap();
+        fetcher.input(2023, 2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,312 @@
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use aoc_core::{
+    input::{self, Source},
+    Answers, Table,
+};
+use clap::{Args, Parser, Subcommand};
+
+use bench::{Baseline, Timings};
+use fetch::{Fetcher, Ureq};
+use run::Run;
+use verify::Status;
+
+mod bench;
+mod fetch;
+mod registry;
+mod run;
+mod verify;
+
+/// Advent of Code 2023 runner.
+#[derive(Debug, Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solves one day, or every registered day with `--all`.
+    Run(RunArgs),
+    /// Checks every registered day against the expected answers.
+    Verify(VerifyArgs),
+    /// Downloads puzzle inputs that are not cached yet.
+    Fetch(FetchArgs),
+    /// Times parsing and both parts of every registered day.
+    Bench(BenchArgs),
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// Day to solve.
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u32>,
+
+    /// Only solve this part.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Input file or `-` for stdin, defaults to `$AOC_INPUT_DIR/dayNN/input.txt`,
+    /// or `dayNN/input.txt` when the variable is unset.
+    #[arg(long, conflicts_with = "all")]
+    input: Option<String>,
+
+    /// Solve every registered day.
+    #[arg(long)]
+    all: bool,
+}
+
+#[derive(Debug, Args)]
+struct VerifyArgs {
+    /// TOML file of expected answers, keyed by `[year.dayNN]` and `partN`.
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+
+    /// Directory holding `dayNN/input.txt`, defaults to `$AOC_INPUT_DIR`
+    /// or the current directory.
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+
+    /// Only verify this day.
+    #[arg(long)]
+    day: Option<u32>,
+}
+
+#[derive(Debug, Args)]
+struct FetchArgs {
+    /// Only fetch this day, defaults to every registered day.
+    #[arg(long)]
+    day: Option<u32>,
+
+    /// Session cookie of a logged in adventofcode.com account.
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: String,
+
+    /// Cache directory, inputs are stored in `{year}/dayNN/input.txt` below it,
+    /// so `$AOC_INPUT_DIR` can point at `{cache_dir}/{year}`.
+    #[arg(long, env = "AOC_CACHE_DIR", default_value = "inputs")]
+    cache_dir: PathBuf,
+
+    /// Server to download from.
+    #[arg(long, env = "AOC_BASE_URL", default_value = fetch::BASE_URL)]
+    base_url: String,
+
+    /// Seconds to wait between two downloads.
+    #[arg(long, default_value_t = 3)]
+    min_interval: u64,
+}
+
+#[derive(Debug, Args)]
+struct BenchArgs {
+    /// Only benchmark this day.
+    #[arg(long)]
+    day: Option<u32>,
+
+    /// Runs of each stage, the median is reported.
+    #[arg(long, default_value_t = 10)]
+    iterations: usize,
+
+    /// JSON file of earlier timings to compare against.
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Write this run's timings as a baseline JSON file.
+    #[arg(long)]
+    save_baseline: Option<PathBuf>,
+
+    /// Slowdown over the baseline, in percent, flagged as a regression.
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+fn main() -> anyhow::Result<ExitCode> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Fetch(args) => fetch(args),
+        Command::Bench(args) => bench(args),
+    }
+}
+
+fn days(number: Option<u32>) -> anyhow::Result<Vec<&'static aoc_core::Day>> {
+    Ok(match number {
+        Some(number) => {
+            vec![registry::find(number)
+                .ok_or(anyhow::anyhow!("Day {} is not registered", number))?]
+        }
+        None => registry::DAYS.iter().collect(),
+    })
+}
+
+fn run(args: RunArgs) -> anyhow::Result<ExitCode> {
+    let mut table = Table::new(&["Day", "Part", "Answer", "Time"]);
+    let mut ok = true;
+
+    for day in days(args.day)? {
+        let source = Source::resolve(
+            day.number,
+            args.input.as_deref(),
+            input::day_path(day.number),
+        );
+        let run = match Run::new(day, &source, args.part) {
+            Ok(run) => run,
+            Err(e) => {
+                ok = false;
+                table.push(vec![
+                    day.number.to_string(),
+                    "-".to_string(),
+                    format!("error: {}", error_chain(&e)),
+                    "-".to_string(),
+                ]);
+                continue;
+            }
+        };
+
+        ok &= run.is_ok();
+        table.push(vec![
+            run.day.to_string(),
+            "parse".to_string(),
+            String::new(),
+            format_duration(run.parse),
+        ]);
+        for part in run.parts {
+            let answer = match part.answer {
+                Ok(answer) => answer,
+                Err(e) => format!("error: {}", error_chain(&e)),
+            };
+            table.push(vec![
+                String::new(),
+                part.part.to_string(),
+                answer,
+                format_duration(part.time),
+            ]);
+        }
+    }
+
+    print!("{}", table);
+    Ok(exit_code(ok))
+}
+
+fn verify(args: VerifyArgs) -> anyhow::Result<ExitCode> {
+    let answers = Answers::load(&args.answers)?;
+    let mut table = Table::new(&["Day", "Part", "Status", "Answer", "Expected"]);
+    let mut ok = true;
+
+    for day in days(args.day)? {
+        let source = match &args.input_dir {
+            Some(dir) => Source::in_dir(dir, day.number),
+            None => Source::resolve(day.number, None, input::day_path(day.number)),
+        };
+
+        for (part, status) in verify::verify(day, &source, &answers) {
+            ok &= !status.is_failure();
+            let (answer, expected) = match &status {
+                Status::Pass => (answers.get(aoc_core::YEAR, day.number, part), None),
+                Status::Fail { expected, actual } => {
+                    (Some(actual.as_str()), Some(expected.as_str()))
+                }
+                Status::Missing { actual } => (Some(actual.as_str()), None),
+                Status::Error(e) => (Some(e.as_str()), None),
+            };
+            table.push(vec![
+                day.number.to_string(),
+                part.to_string(),
+                status.to_string(),
+                answer.unwrap_or_default().to_string(),
+                expected.unwrap_or_default().to_string(),
+            ]);
+        }
+    }
+
+    print!("{}", table);
+    Ok(exit_code(ok))
+}
+
+fn fetch(args: FetchArgs) -> anyhow::Result<ExitCode> {
+    let fetcher = Fetcher::new(Ureq::default(), args.session, args.cache_dir)
+        .base_url(&args.base_url)
+        .min_interval(Duration::from_secs(args.min_interval));
+    for day in days(args.day)? {
+        let path = fetcher.input(aoc_core::YEAR, day.number)?;
+        println!("Day {:02}: {}", day.number, path.display());
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn bench(args: BenchArgs) -> anyhow::Result<ExitCode> {
+    let baseline = args.baseline.as_deref().map(Baseline::load).transpose()?;
+    let mut current = Baseline::default();
+    let mut table = Table::new(&["Day", "Stage", "Median", "Baseline", "Change"]);
+    let mut ok = true;
+
+    for day in days(args.day)? {
+        let source = Source::resolve(day.number, None, input::day_path(day.number));
+        let timings = match Timings::measure(day, &source, args.iterations) {
+            Ok(timings) => timings,
+            Err(e) => {
+                ok = false;
+                table.push(vec![
+                    day.number.to_string(),
+                    "-".to_string(),
+                    format!("error: {}", error_chain(&e)),
+                ]);
+                continue;
+            }
+        };
+        current.days.insert(day.number, timings);
+
+        let before = baseline.as_ref().and_then(|b| b.days.get(&day.number));
+        for (i, (stage, nanos)) in Timings::STAGES.iter().zip(timings.stages()).enumerate() {
+            let mut row = vec![
+                if i == 0 {
+                    day.number.to_string()
+                } else {
+                    String::new()
+                },
+                stage.to_string(),
+                format_duration(Duration::from_nanos(nanos)),
+            ];
+            if let Some(before) = before {
+                let before = before.stages()[i];
+                let mut change = format!("{:+.1}%", bench::change(before, nanos));
+                if bench::is_regression(before, nanos, args.threshold) {
+                    ok = false;
+                    change.push_str(" REGRESSION");
+                }
+                row.push(format_duration(Duration::from_nanos(before)));
+                row.push(change);
+            }
+            table.push(row);
+        }
+    }
+
+    print!("{}", table);
+    if let Some(path) = &args.save_baseline {
+        current.save(path)?;
+    }
+    Ok(exit_code(ok))
+}
+
+fn exit_code(ok: bool) -> ExitCode {
+    match ok {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+    while let Some(e) = source {
+        message.push_str(": ");
+        message.push_str(&e.to_string());
+        source = e.source();
+    }
+    message
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.3?}", d)
+}
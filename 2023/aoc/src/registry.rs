@@ -0,0 +1,8 @@
+use aoc_core::Day;
+
+/// Every day the runner knows about, in order.
+pub const DAYS: &[Day] = &[day01::DAY, day02::DAY, day03::DAY, day04::DAY, day05::DAY];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
@@ -0,0 +1,56 @@
+use std::time::{Duration, Instant};
+
+use aoc_core::{input::Source, Day, Result};
+
+/// Answers and timings of a single day's run.
+#[derive(Debug)]
+pub struct Run {
+    pub day: u32,
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: u8,
+    pub answer: Result<String>,
+    pub time: Duration,
+}
+
+impl Run {
+    /// Runs the given parts of `day`, or both when `part` is `None`.
+    pub fn new(day: &Day, source: &Source, part: Option<u8>) -> Result<Self> {
+        let input = source.read(day.number)?;
+
+        let start = Instant::now();
+        let solution = day.parse(&input)?;
+        let parse = start.elapsed();
+
+        let parts = [1, 2]
+            .into_iter()
+            .filter(|p| part.is_none_or(|part| part == *p))
+            .map(|p| {
+                let start = Instant::now();
+                let answer = match p {
+                    1 => solution.part1(),
+                    _ => solution.part2(),
+                };
+                PartRun {
+                    part: p,
+                    answer,
+                    time: start.elapsed(),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            day: day.number,
+            parse,
+            parts,
+        })
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.parts.iter().all(|p| p.answer.is_ok())
+    }
+}
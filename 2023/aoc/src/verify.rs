@@ -0,0 +1,114 @@
+use std::fmt;
+
+use aoc_core::{input::Source, Answers, Day, YEAR};
+
+use crate::run::Run;
+
+/// Outcome of checking one part against the expected answers.
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    Missing { actual: String },
+    Error(String),
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Fail { .. } | Self::Error(_))
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail { .. } => write!(f, "FAIL"),
+            Self::Missing { .. } => write!(f, "missing"),
+            Self::Error(_) => write!(f, "ERROR"),
+        }
+    }
+}
+
+/// Solves both parts of `day` and checks them against `answers`.
+pub fn verify(day: &Day, source: &Source, answers: &Answers) -> Vec<(u8, Status)> {
+    let run = match Run::new(day, source, None) {
+        Ok(run) => run,
+        Err(e) => {
+            let message = crate::error_chain(&e);
+            return vec![
+                (1, Status::Error(message.clone())),
+                (2, Status::Error(message)),
+            ];
+        }
+    };
+
+    run.parts
+        .into_iter()
+        .map(|part| {
+            let status = match (part.answer, answers.get(YEAR, day.number, part.part)) {
+                (Err(e), _) => Status::Error(crate::error_chain(&e)),
+                (Ok(actual), None) => Status::Missing { actual },
+                (Ok(actual), Some(expected)) if actual == expected => Status::Pass,
+                (Ok(actual), Some(expected)) => Status::Fail {
+                    expected: expected.to_string(),
+                    actual,
+                },
+            };
+            (part.part, status)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn samples() {
+        let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("../samples");
+        let answers = Answers::load(samples.join("answers.toml")).unwrap();
+
+        for day in registry::DAYS {
+            for (part, status) in verify(day, &Source::in_dir(&samples, day.number), &answers) {
+                assert!(
+                    !status.is_failure(),
+                    "day {} part {}: {:?}",
+                    day.number,
+                    part,
+                    status
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mismatch() {
+        let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("../samples");
+        let answers: Answers = "[2023.day04]\npart1 = 14".parse().unwrap();
+        let day = registry::find(4).unwrap();
+
+        let statuses = verify(day, &Source::in_dir(&samples, 4), &answers);
+        assert_eq!(
+            statuses,
+            vec![
+                (
+                    1,
+                    Status::Fail {
+                        expected: "14".to_string(),
+                        actual: "13".to_string()
+                    }
+                ),
+                (
+                    2,
+                    Status::Missing {
+                        actual: "30".to_string()
+                    }
+                ),
+            ]
+        );
+    }
+}
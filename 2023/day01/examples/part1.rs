@@ -0,0 +1,81 @@
+//! Times part 1 on generated calibration lines with the regexes it used to
+//! run, the tokenizer and the byte scan of `Trebuchet::from_str1`:
+//!
+//! ```text
+//! cargo run --release -p day01 [--features memchr] --example part1 [MEGABYTES]
+//! ```
+
+use std::time::{Duration, Instant};
+
+use day01::{DigitVocabulary, Policy, Trebuchet};
+use regex::Regex;
+
+fn main() -> anyhow::Result<()> {
+    let megabytes: usize = match std::env::args().nth(1) {
+        Some(n) => n.parse()?,
+        None => 1024,
+    };
+    let input = generate(megabytes << 20);
+
+    let (sum, time) = timed(|| regex_sum(&input));
+    report("regex", sum, time, input.len());
+    let (sum, time) = timed(|| {
+        Trebuchet::from_str_with(&input, &DigitVocabulary::default(), Policy::Strict)
+            .unwrap()
+            .sum()
+    });
+    report("tokenizer", sum, time, input.len());
+    let (sum, time) = timed(|| Trebuchet::from_str1(&input).unwrap().sum());
+    report("byte scan", sum, time, input.len());
+    Ok(())
+}
+
+/// Lines of 1 to 60 lowercase letters and digits, each with a digit.
+fn generate(len: usize) -> String {
+    let mut state = 0x2023_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let mut s = String::with_capacity(len + 64);
+    while s.len() < len {
+        let line_len = 1 + next() % 60;
+        let digit_at = next() % line_len;
+        for i in 0..line_len {
+            let r = next();
+            if i == digit_at || r % 10 == 0 {
+                s.push((b'0' + (r % 10) as u8) as char);
+            } else {
+                s.push((b'a' + (r % 26) as u8) as char);
+            }
+        }
+        s.push('\n');
+    }
+    s
+}
+
+fn regex_sum(s: &str) -> u64 {
+    let first = Regex::new(r"^.*?(\d)").unwrap();
+    let last = Regex::new(r"^.*(\d)").unwrap();
+    let digit = |re: &Regex, line| -> u64 { re.captures(line).unwrap()[1].parse().unwrap() };
+    s.lines()
+        .map(|line| digit(&first, line) * 10 + digit(&last, line))
+        .sum()
+}
+
+fn timed(f: impl FnOnce() -> u64) -> (u64, Duration) {
+    let start = Instant::now();
+    let sum = f();
+    (sum, start.elapsed())
+}
+
+fn report(name: &str, sum: u64, time: Duration, len: usize) {
+    let throughput = len as f64 / time.as_secs_f64() / (1 << 30) as f64;
+    println!(
+        "{:<10} {:>10.3?} {:>7.2} GiB/s  sum {}",
+        name, time, throughput, sum
+    );
+}
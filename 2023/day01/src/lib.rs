@@ -0,0 +1,42 @@
+use aoc_core::{Day, Result, Solution};
+
+pub use report::LineReport;
+pub use stream::{CalibrationError, Calibrations};
+pub use token::{tokenize, Token};
+pub use trebuchet::{Policy, Trebuchet};
+pub use vocabulary::DigitVocabulary;
+
+mod report;
+mod stream;
+mod token;
+mod trebuchet;
+mod vocabulary;
+
+pub const DAY: Day = Day::new::<Day01>();
+
+pub struct Day01 {
+    digits: Trebuchet,
+    spelled: Trebuchet,
+}
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self {
+            digits: Trebuchet::from_str1(input)?,
+            spelled: Trebuchet::from_str2(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<u64> {
+        Ok(self.digits.sum())
+    }
+
+    fn part2(&self) -> Result<u64> {
+        Ok(self.spelled.sum())
+    }
+}
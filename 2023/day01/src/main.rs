@@ -1,19 +1,96 @@
-use std::fs::read_to_string;
+use aoc_core::{input::Source, Error, Solution, Table};
+use day01::{Day01, DigitVocabulary, LineReport, Token, Trebuchet};
+use serde_json::{json, Value};
 
-use trebuchet::Trebuchet;
-
-mod trebuchet;
+const USAGE: &str = "usage: day01 [INPUT | -] [--report [table | json]]";
 
+/// `day01 [INPUT | -] [--report [table | json]]`, printing how each line
+/// gets its part 1 and part 2 values instead of the answers with `--report`.
 fn main() -> anyhow::Result<()> {
-    let s = read_to_string("input.txt")?;
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(i) = args.iter().position(|arg| arg == "--report") else {
+        aoc_core::run::<Day01>()?;
+        return Ok(());
+    };
+
+    let (input, format) = (&args[..i], &args[i + 1..]);
+    let json = match format {
+        [] => false,
+        [format] if format == "table" => false,
+        [format] if format == "json" => true,
+        _ => return Err(Error::Usage(USAGE.to_string()).into()),
+    };
+    if input.len() > 1 {
+        return Err(Error::Usage(USAGE.to_string()).into());
+    }
 
-    let t = Trebuchet::from_str1(&s)?;
-    let sum = t.sum();
-    println!("Sum 1: {}", sum);
+    let source = Source::resolve(Day01::DAY, input.first().map(String::as_str), "input.txt");
+    let input = source.read(Day01::DAY)?;
+    let (digits, english) = (DigitVocabulary::default(), DigitVocabulary::english());
+    let reports = input
+        .lines()
+        .zip(Trebuchet::report(&input, &digits))
+        .zip(Trebuchet::report(&input, &english));
 
-    let t = Trebuchet::from_str2(&s)?;
-    let sum = t.sum();
-    println!("Sum 2: {}", sum);
+    if json {
+        let lines: Vec<Value> = reports
+            .map(|((line, part1), part2)| {
+                json!({
+                    "line": part1.line,
+                    "text": line,
+                    "part1": json_part(line, &part1),
+                    "part2": json_part(line, &part2),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&lines)?);
+        return Ok(());
+    }
 
+    let mut table = Table::new(&[
+        "Line", "Part 1", "First", "Last", "Part 2", "First", "Last", "Diff",
+    ]);
+    for ((line, part1), part2) in reports {
+        table.push(vec![
+            part1.line.to_string(),
+            cell(part1.value),
+            token_cell(line, part1.first),
+            token_cell(line, part1.last),
+            cell(part2.value),
+            token_cell(line, part2.first),
+            token_cell(line, part2.last),
+            if part1.value == part2.value { "" } else { "*" }.to_string(),
+        ]);
+    }
+    print!("{}", table);
     Ok(())
 }
+
+fn cell(value: Option<u64>) -> String {
+    value.map_or("-".to_string(), |v| v.to_string())
+}
+
+/// `text@offset` of a token.
+fn token_cell(line: &str, token: Option<Token>) -> String {
+    token.map_or("-".to_string(), |t| {
+        format!("{}@{}", &line[t.start..t.end], t.start)
+    })
+}
+
+fn json_part(line: &str, report: &LineReport) -> Value {
+    let token = |token: Option<Token>| {
+        token.map(|t| {
+            json!({
+                "text": &line[t.start..t.end],
+                "start": t.start,
+                "end": t.end,
+                "value": t.value,
+            })
+        })
+    };
+    json!({
+        "value": report.value,
+        "first": token(report.first),
+        "last": token(report.last),
+    })
+}
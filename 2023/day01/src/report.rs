@@ -0,0 +1,67 @@
+use crate::{DigitVocabulary, Token};
+
+/// The tokens a line's calibration value comes from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineReport {
+    /// 1-based line number.
+    pub line: usize,
+    pub first: Option<Token>,
+    pub last: Option<Token>,
+    /// `None` when the line has no digit.
+    pub value: Option<u64>,
+}
+
+impl LineReport {
+    pub fn new(number: usize, line: &str, vocabulary: &DigitVocabulary) -> Self {
+        let calibration = calibration(line, vocabulary);
+        Self {
+            line: number,
+            first: calibration.map(|(first, _, _)| first),
+            last: calibration.map(|(_, last, _)| last),
+            value: calibration.map(|(_, _, value)| value),
+        }
+    }
+}
+
+/// The first and last token of `line`, and the calibration value they make,
+/// `None` when the line has no digit.
+pub(crate) fn calibration(line: &str, vocabulary: &DigitVocabulary) -> Option<(Token, Token, u64)> {
+    let tokens = vocabulary.tokenize(line);
+    let (first, last) = (*tokens.first()?, *tokens.last()?);
+    Some((first, last, first.first_digit() * 10 + last.last_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Trebuchet;
+
+    #[test]
+    fn report() {
+        let s = "two1nine\nxtwone3four\nnothing";
+        let reports: Vec<LineReport> = Trebuchet::report(s, &DigitVocabulary::english()).collect();
+
+        let bounds = |t: Option<Token>| t.map(|t| (t.value, t.start, t.end));
+        assert_eq!(reports[0].line, 1);
+        assert_eq!(bounds(reports[0].first), Some((2, 0, 3)));
+        assert_eq!(bounds(reports[0].last), Some((9, 4, 8)));
+        assert_eq!(reports[0].value, Some(29));
+        assert_eq!(bounds(reports[1].first), Some((2, 1, 4)));
+        assert_eq!(bounds(reports[1].last), Some((4, 7, 11)));
+        assert_eq!(reports[1].value, Some(24));
+        assert_eq!(
+            reports[2],
+            LineReport {
+                line: 3,
+                first: None,
+                last: None,
+                value: None
+            }
+        );
+
+        let digits: Vec<Option<u64>> = Trebuchet::report(s, &DigitVocabulary::default())
+            .map(|r| r.value)
+            .collect();
+        assert_eq!(digits, [Some(11), Some(33), None]);
+    }
+}
@@ -0,0 +1,128 @@
+use std::io::{self, BufRead};
+
+use aoc_core::ParseError;
+
+use crate::{DigitVocabulary, Trebuchet};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CalibrationError {
+    #[error("failed reading calibration lines")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// Calibration values of the lines of a reader, holding only one line in
+/// memory, so any size of input sums in constant memory:
+/// `Calibrations::new(reader, vocabulary).sum::<Result<u64, _>>()`.
+///
+/// A line without a digit yields its error and the lines after it are
+/// still read. Reading stops at the first I/O error.
+#[derive(Debug)]
+pub struct Calibrations<R> {
+    reader: R,
+    vocabulary: DigitVocabulary,
+    buf: String,
+    line: usize,
+    done: bool,
+}
+
+impl<R: BufRead> Calibrations<R> {
+    pub fn new(reader: R, vocabulary: DigitVocabulary) -> Self {
+        Self {
+            reader,
+            vocabulary,
+            buf: String::new(),
+            line: 0,
+            done: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Calibrations<R> {
+    type Item = Result<u64, CalibrationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        self.buf.clear();
+        match self.reader.read_line(&mut self.buf) {
+            Ok(0) => {
+                self.done = true;
+                return None;
+            }
+            Ok(_) => self.line += 1,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e.into()));
+            }
+        }
+
+        let line = self.buf.strip_suffix('\n').unwrap_or(&self.buf);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        Some(Trebuchet::value(line, &self.vocabulary).map_err(|e| e.line(self.line).into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Policy;
+
+    const TEST_INPUT: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    #[test]
+    fn values() {
+        let values = Calibrations::new(TEST_INPUT.as_bytes(), DigitVocabulary::english())
+            .collect::<Result<Vec<u64>, _>>()
+            .unwrap();
+        assert_eq!(values, [29, 83, 13, 24, 42, 14, 76]);
+
+        let sum = Calibrations::new("1abc2\r\n\npqr3stu8vwx\n".as_bytes(), Default::default())
+            .filter_map(Result::ok)
+            .sum::<u64>();
+        assert_eq!(sum, 12 + 38);
+    }
+
+    #[test]
+    fn matches_trebuchet() {
+        let vocabulary = DigitVocabulary::english();
+        let sum = Calibrations::new(TEST_INPUT.as_bytes(), vocabulary.clone())
+            .sum::<Result<u64, _>>()
+            .unwrap();
+        assert_eq!(
+            sum,
+            Trebuchet::from_str_with(TEST_INPUT, &vocabulary, Policy::Strict)
+                .unwrap()
+                .sum()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let mut values = Calibrations::new("1\nnone\n2".as_bytes(), Default::default());
+        assert_eq!(values.next().unwrap().unwrap(), 11);
+        match values.next().unwrap() {
+            Err(CalibrationError::Parse(e)) => {
+                assert_eq!((e.line, e.snippet.as_str()), (2, "none"))
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert_eq!(values.next().unwrap().unwrap(), 22);
+        assert!(values.next().is_none());
+
+        let mut values =
+            Calibrations::new(io::BufReader::new(&[0xff, b'\n'][..]), Default::default());
+        assert!(matches!(values.next(), Some(Err(CalibrationError::Io(_)))));
+        assert!(values.next().is_none());
+    }
+}
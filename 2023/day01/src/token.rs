@@ -0,0 +1,92 @@
+use std::sync::LazyLock;
+
+use crate::DigitVocabulary;
+
+static ENGLISH: LazyLock<DigitVocabulary> = LazyLock::new(DigitVocabulary::english);
+
+/// A number in a calibration line, a single ASCII numeral or a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub value: u64,
+    /// Byte offset of the token in its line.
+    pub start: usize,
+    /// Byte offset just past the token.
+    pub end: usize,
+    pub spelled: bool,
+}
+
+impl Token {
+    /// Leading decimal digit of the value.
+    pub fn first_digit(&self) -> u64 {
+        let mut value = self.value;
+        while value >= 10 {
+            value /= 10;
+        }
+        value
+    }
+
+    pub fn last_digit(&self) -> u64 {
+        self.value % 10
+    }
+}
+
+/// Every digit token of `line` by position, with the puzzle's English
+/// words, see [`DigitVocabulary::tokenize`].
+pub fn tokenize(line: &str) -> Vec<Token> {
+    ENGLISH.tokenize(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(line: &str) -> Vec<(u64, usize)> {
+        tokenize(line).iter().map(|t| (t.value, t.start)).collect()
+    }
+
+    #[test]
+    fn overlapping_words() {
+        assert_eq!(digits("eightwothree"), [(8, 0), (2, 4), (3, 7)]);
+        assert_eq!(digits("xtwone3four"), [(2, 1), (1, 3), (3, 6), (4, 7)]);
+        assert_eq!(digits("oneight"), [(1, 0), (8, 2)]);
+        assert_eq!(digits("treb7uchet"), [(7, 4)]);
+        assert_eq!(digits("nothing"), []);
+    }
+
+    #[test]
+    fn token_bounds() {
+        let line = "a1seven";
+        let tokens = tokenize(line);
+        assert_eq!(
+            tokens,
+            [
+                Token {
+                    value: 1,
+                    start: 1,
+                    end: 2,
+                    spelled: false
+                },
+                Token {
+                    value: 7,
+                    start: 2,
+                    end: 7,
+                    spelled: true
+                }
+            ]
+        );
+        assert_eq!(&line[tokens[1].start..tokens[1].end], "seven");
+    }
+
+    #[test]
+    fn digits_of_value() {
+        let token = |value| Token {
+            value,
+            start: 0,
+            end: 1,
+            spelled: true,
+        };
+        assert_eq!((token(7).first_digit(), token(7).last_digit()), (7, 7));
+        assert_eq!((token(0).first_digit(), token(0).last_digit()), (0, 0));
+        assert_eq!((token(120).first_digit(), token(120).last_digit()), (1, 0));
+    }
+}
@@ -1,94 +1,172 @@
-use regex::Regex;
+use aoc_core::{ParseError, Solution};
 
-#[derive(Debug, Clone, PartialEq)]
+use crate::{report::calibration, Day01, DigitVocabulary, LineReport};
+
+/// What to do with a line without any digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Policy {
+    /// Fail the whole parse.
+    #[default]
+    Strict,
+    /// Leave the line out.
+    Skip,
+    /// Count the line as 0.
+    Zero,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct Trebuchet {
     values: Vec<u64>,
+    skipped: Vec<usize>,
 }
 
 impl Trebuchet {
     pub fn new(values: Vec<u64>) -> Self {
-        Self { values }
+        Self {
+            values,
+            skipped: Vec::new(),
+        }
     }
 
     pub fn sum(&self) -> u64 {
         self.values.iter().sum()
     }
 
-    pub fn from_str1(s: &str) -> anyhow::Result<Self> {
-        let first = Regex::new(r"^.*?(\d)").unwrap();
-        let second = Regex::new(r"^.*(\d)").unwrap();
-        Self::from_str_with_regex(s, first, second)
-    }
-
-    pub fn from_str2(s: &str) -> anyhow::Result<Self> {
-        let first = Regex::new(r"^.*?(\d|one|two|three|four|five|six|seven|eight|nine)").unwrap();
-        let second = Regex::new(r"^.*(\d|one|two|three|four|five|six|seven|eight|nine)").unwrap();
-
-        let mut values = Vec::with_capacity(s.lines().count());
-        for line in s.lines() {
-            let first_digit = first
-                .captures(line)
-                .ok_or(anyhow::anyhow!("No first digit found"))?
-                .get(1)
-                .unwrap()
-                .as_str();
-            let first_digit = Self::parse_str_digit(first_digit)?;
-            let second_digit = second
-                .captures(line)
-                .ok_or(anyhow::anyhow!("No second digit found"))?
-                .get(1)
-                .unwrap()
-                .as_str();
-            let second_digit = Self::parse_str_digit(second_digit)?;
-            let value = first_digit * 10 + second_digit;
-            values.push(value);
+    /// 1-based numbers of the lines without a digit, left out or counted
+    /// as 0 depending on the [`Policy`].
+    pub fn skipped(&self) -> &[usize] {
+        &self.skipped
+    }
+
+    /// Calibration values from the first and last ASCII digit of each line.
+    pub fn from_str1(s: &str) -> Result<Self, ParseError> {
+        Self::from_str1_with(s, Policy::Strict)
+    }
+
+    /// Like [`Trebuchet::from_str1`], scanning each line's bytes from both
+    /// ends instead of tokenizing it.
+    pub fn from_str1_with(s: &str, policy: Policy) -> Result<Self, ParseError> {
+        let values = Lines(s).map(|line| {
+            let first = line.bytes().find(u8::is_ascii_digit);
+            let last = line.bytes().rfind(u8::is_ascii_digit);
+            match first.zip(last) {
+                Some((first, last)) => Ok(((first - b'0') * 10 + last - b'0') as u64),
+                None => Err(ParseError::new(Day01::DAY, line, 0, line.len(), "a digit")),
+            }
+        });
+        Self::from_values(values, policy)
+    }
+
+    /// Calibration values from the first and last digit of each line,
+    /// ASCII or spelled out in English.
+    pub fn from_str2(s: &str) -> Result<Self, ParseError> {
+        Self::from_str_with(s, &DigitVocabulary::english(), Policy::Strict)
+    }
+
+    /// Calibration values from the first and last digit of each line,
+    /// ASCII or spelled out with `vocabulary`.
+    pub fn from_str_with(
+        s: &str,
+        vocabulary: &DigitVocabulary,
+        policy: Policy,
+    ) -> Result<Self, ParseError> {
+        let values = s.lines().map(|line| Self::value(line, vocabulary));
+        Self::from_values(values, policy)
+    }
+
+    /// Collects the values of consecutive lines, applying `policy` to the
+    /// ones without a digit.
+    fn from_values(
+        values: impl Iterator<Item = Result<u64, ParseError>>,
+        policy: Policy,
+    ) -> Result<Self, ParseError> {
+        let mut trebuchet = Self::default();
+        for (i, value) in values.enumerate() {
+            match (value, policy) {
+                (Ok(value), _) => trebuchet.values.push(value),
+                (Err(e), Policy::Strict) => return Err(e.line(i + 1)),
+                (Err(_), Policy::Skip) => trebuchet.skipped.push(i + 1),
+                (Err(_), Policy::Zero) => {
+                    trebuchet.values.push(0);
+                    trebuchet.skipped.push(i + 1);
+                }
+            }
         }
-        Ok(Self::new(values))
-    }
-
-    fn parse_str_digit(digit: &str) -> anyhow::Result<u64> {
-        let digit = match digit {
-            "one" => "1",
-            "two" => "2",
-            "three" => "3",
-            "four" => "4",
-            "five" => "5",
-            "six" => "6",
-            "seven" => "7",
-            "eight" => "8",
-            "nine" => "9",
-            _ => digit,
-        };
-        Ok(digit.parse::<u64>()?)
-    }
-
-    fn from_str_with_regex(s: &str, first: Regex, second: Regex) -> anyhow::Result<Self> {
-        let mut values = Vec::with_capacity(s.lines().count());
-        for line in s.lines() {
-            let first_digit = first
-                .captures(line)
-                .ok_or(anyhow::anyhow!("No first digit found"))?
-                .get(1)
-                .unwrap()
-                .as_str()
-                .parse::<u64>()?;
-            let second_digit = second
-                .captures(line)
-                .ok_or(anyhow::anyhow!("No second digit found"))?
-                .get(1)
-                .unwrap()
-                .as_str()
-                .parse::<u64>()?;
-            let value = first_digit * 10 + second_digit;
-            values.push(value);
+        Ok(trebuchet)
+    }
+
+    /// Calibration value of a single line, from its first and last digit.
+    pub fn value(line: &str, vocabulary: &DigitVocabulary) -> Result<u64, ParseError> {
+        calibration(line, vocabulary)
+            .map(|(_, _, value)| value)
+            .ok_or_else(|| ParseError::new(Day01::DAY, line, 0, line.len(), "a digit"))
+    }
+
+    /// How every line of `s` gets its calibration value, lines without a
+    /// digit included.
+    pub fn report<'a>(
+        s: &'a str,
+        vocabulary: &'a DigitVocabulary,
+    ) -> impl Iterator<Item = LineReport> + 'a {
+        s.lines()
+            .enumerate()
+            .map(|(i, line)| LineReport::new(i + 1, line, vocabulary))
+    }
+}
+
+/// The lines of a string like [`str::lines`], found with memchr's
+/// vectorized search when the `memchr` feature is enabled.
+struct Lines<'a>(&'a str);
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.0.is_empty() {
+            return None;
         }
-        Ok(Self::new(values))
+
+        #[cfg(feature = "memchr")]
+        let end = memchr::memchr(b'\n', self.0.as_bytes());
+        #[cfg(not(feature = "memchr"))]
+        let end = self.0.bytes().position(|b| b == b'\n');
+
+        let (line, rest) = match end {
+            Some(end) => (&self.0[..end], &self.0[end + 1..]),
+            None => (self.0, ""),
+        };
+        self.0 = rest;
+        Some(line.strip_suffix('\r').unwrap_or(line))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use regex::Regex;
+
+    /// The regexes the tokenizer replaced, lazy and greedy prefixes finding
+    /// the first and last digit.
+    const DIGIT: &str = r"\d";
+    const SPELLED: &str = r"\d|one|two|three|four|five|six|seven|eight|nine";
+
+    fn regex_values(s: &str, digit: &str) -> Vec<u64> {
+        let first = Regex::new(&format!("^.*?({})", digit)).unwrap();
+        let last = Regex::new(&format!("^.*({})", digit)).unwrap();
+        let value = |re: &Regex, line| {
+            let m = re.captures(line).unwrap().get(1).unwrap().as_str();
+            let words = [
+                "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+            ];
+            match words.iter().position(|w| *w == m) {
+                Some(i) => i as u64 + 1,
+                None => m.parse().unwrap(),
+            }
+        };
+        s.lines()
+            .map(|line| value(&first, line) * 10 + value(&last, line))
+            .collect()
+    }
 
     #[test]
     fn test_from_str() {
@@ -101,7 +179,8 @@ treb7uchet
         assert_eq!(
             trebuchet,
             Trebuchet {
-                values: vec![12, 38, 15, 77]
+                values: vec![12, 38, 15, 77],
+                skipped: vec![],
             }
         )
     }
@@ -119,8 +198,107 @@ zoneight234
         assert_eq!(
             trebuchet,
             Trebuchet {
-                values: vec![29, 83, 13, 24, 42, 14, 76]
+                values: vec![29, 83, 13, 24, 42, 14, 76],
+                skipped: vec![],
             }
         )
     }
+
+    #[test]
+    fn line_without_digits() {
+        let e = Trebuchet::from_str1("1abc2\ntrebuchet\n").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 1, line 2, column 1: expected a digit, found `trebuchet`"
+        );
+    }
+
+    #[test]
+    fn matches_regex() {
+        let s = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+1oneight
+twone
+9sevenine
+5";
+        let with_digits: Vec<&str> = s
+            .lines()
+            .filter(|line| line.bytes().any(|b| b.is_ascii_digit()))
+            .collect();
+        let with_digits = with_digits.join("\n");
+        assert_eq!(
+            Trebuchet::from_str1(&with_digits).unwrap().values,
+            regex_values(&with_digits, DIGIT)
+        );
+        assert_eq!(
+            Trebuchet::from_str2(s).unwrap().values,
+            regex_values(s, SPELLED)
+        );
+    }
+
+    #[test]
+    fn localized() {
+        let s = "dois3nove\nxcinco";
+        let trebuchet =
+            Trebuchet::from_str_with(s, &DigitVocabulary::portuguese(), Policy::Strict).unwrap();
+        assert_eq!(trebuchet.values, [29, 55]);
+
+        let vocabulary = DigitVocabulary::english()
+            .with("zero", 0)
+            .with("twelve", 12);
+        let trebuchet =
+            Trebuchet::from_str_with("zero5\ntwelve\n4twelvex", &vocabulary, Policy::Strict)
+                .unwrap();
+        assert_eq!(trebuchet.values, [5, 12, 42]);
+    }
+
+    #[test]
+    fn lines() {
+        for s in ["", "\n", "a", "a\n", "a\r\nb", "a\n\nb\n", "\r\n\r\n"] {
+            assert_eq!(Lines(s).collect::<Vec<_>>(), s.lines().collect::<Vec<_>>());
+        }
+
+        let e = Trebuchet::from_str1("1\r\n\r\n2").unwrap_err();
+        assert_eq!((e.line, e.snippet.as_str()), (2, ""));
+    }
+
+    #[test]
+    fn byte_scan_matches_tokenizer() {
+        let s = "1abc2\r\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\nseven9\n";
+        assert_eq!(
+            Trebuchet::from_str1(s).unwrap(),
+            Trebuchet::from_str_with(s, &DigitVocabulary::default(), Policy::Strict).unwrap()
+        );
+    }
+
+    #[test]
+    fn policies() {
+        let s = "1abc2\nnothing\n\ntreb7uchet";
+        for spelled in [false, true] {
+            let parse = |policy| match spelled {
+                false => Trebuchet::from_str1_with(s, policy),
+                true => Trebuchet::from_str_with(s, &DigitVocabulary::english(), policy),
+            };
+            let e = parse(Policy::Strict).unwrap_err();
+            assert_eq!(e.line, 2);
+
+            let skip = parse(Policy::Skip).unwrap();
+            assert_eq!(
+                (skip.values.as_slice(), skip.skipped()),
+                (&[12, 77][..], &[2, 3][..])
+            );
+
+            let zero = parse(Policy::Zero).unwrap();
+            assert_eq!(
+                (zero.values.as_slice(), zero.skipped()),
+                (&[12, 0, 0, 77][..], &[2, 3][..])
+            );
+            assert_eq!(zero.sum(), skip.sum());
+        }
+    }
 }
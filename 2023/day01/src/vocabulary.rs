@@ -0,0 +1,170 @@
+use crate::Token;
+
+/// The words spelling out numbers in calibration lines, besides the ASCII
+/// numerals that are always digits.
+///
+/// A word may stand for any number, `zero` for 0 or `twelve` for 12, whose
+/// decimal digits then count as the digits of the line.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DigitVocabulary {
+    words: Vec<(String, u64)>,
+}
+
+impl DigitVocabulary {
+    /// A vocabulary of `(word, number)` pairs, later pairs replacing the
+    /// number of an earlier identical word.
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = (S, u64)>) -> Self {
+        words
+            .into_iter()
+            .fold(Self::default(), |vocabulary, (word, number)| {
+                vocabulary.with(word, number)
+            })
+    }
+
+    /// `one` to `nine`, the words of the puzzle, without `zero`.
+    pub fn english() -> Self {
+        Self::new([
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+        ])
+    }
+
+    pub fn portuguese() -> Self {
+        Self::new([
+            ("zero", 0),
+            ("um", 1),
+            ("dois", 2),
+            ("três", 3),
+            ("quatro", 4),
+            ("cinco", 5),
+            ("seis", 6),
+            ("sete", 7),
+            ("oito", 8),
+            ("nove", 9),
+        ])
+    }
+
+    pub fn spanish() -> Self {
+        Self::new([
+            ("cero", 0),
+            ("uno", 1),
+            ("dos", 2),
+            ("tres", 3),
+            ("cuatro", 4),
+            ("cinco", 5),
+            ("seis", 6),
+            ("siete", 7),
+            ("ocho", 8),
+            ("nueve", 9),
+        ])
+    }
+
+    pub fn german() -> Self {
+        Self::new([
+            ("null", 0),
+            ("eins", 1),
+            ("zwei", 2),
+            ("drei", 3),
+            ("vier", 4),
+            ("fünf", 5),
+            ("sechs", 6),
+            ("sieben", 7),
+            ("acht", 8),
+            ("neun", 9),
+        ])
+    }
+
+    /// Adds `word` as spelling `number`, replacing its earlier number.
+    ///
+    /// # Panics
+    ///
+    /// If `word` is empty, as it would match everywhere.
+    pub fn with(mut self, word: impl Into<String>, number: u64) -> Self {
+        let word = word.into();
+        assert!(!word.is_empty(), "digit words must not be empty");
+        match self.words.iter_mut().find(|(w, _)| *w == word) {
+            Some((_, n)) => *n = number,
+            None => self.words.push((word, number)),
+        }
+        self
+    }
+
+    /// Every numeral and word of `line` by position, in one pass.
+    /// Overlapping words are all kept, so `eightwo` gives both `eight`
+    /// and `two`.
+    pub fn tokenize(&self, line: &str) -> Vec<Token> {
+        let bytes = line.as_bytes();
+        let mut tokens = Vec::new();
+
+        for start in 0..bytes.len() {
+            if bytes[start].is_ascii_digit() {
+                tokens.push(Token {
+                    value: (bytes[start] - b'0') as u64,
+                    start,
+                    end: start + 1,
+                    spelled: false,
+                });
+                continue;
+            }
+            for (word, value) in &self.words {
+                if bytes[start..].starts_with(word.as_bytes()) {
+                    tokens.push(Token {
+                        value: *value,
+                        start,
+                        end: start + word.len(),
+                        spelled: true,
+                    });
+                }
+            }
+        }
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(vocabulary: &DigitVocabulary, line: &str) -> Vec<u64> {
+        vocabulary.tokenize(line).iter().map(|t| t.value).collect()
+    }
+
+    #[test]
+    fn languages() {
+        assert_eq!(values(&DigitVocabulary::english(), "zeroneight"), [1, 8]);
+        assert_eq!(values(&DigitVocabulary::portuguese(), "umtrês7"), [1, 3, 7]);
+        assert_eq!(
+            values(&DigitVocabulary::spanish(), "cerotresuno"),
+            [0, 3, 1]
+        );
+        assert_eq!(
+            values(&DigitVocabulary::german(), "xfünfnullacht"),
+            [5, 0, 8]
+        );
+        assert_eq!(values(&DigitVocabulary::default(), "one2three"), [2]);
+    }
+
+    #[test]
+    fn custom() {
+        let vocabulary = DigitVocabulary::new([("ten", 10), ("twelve", 12)])
+            .with("zero", 0)
+            .with("ten", 100);
+        assert_eq!(values(&vocabulary, "tenzerotwelve"), [100, 0, 12]);
+
+        let tokens = vocabulary.tokenize("a3twelve");
+        assert_eq!((tokens[1].start, tokens[1].end), (2, 8));
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be empty")]
+    fn empty_word() {
+        let _ = DigitVocabulary::default().with("", 1);
+    }
+}
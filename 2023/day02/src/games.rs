@@ -1,18 +1,27 @@
-use regex::Regex;
+use aoc_core::{
+    parse::{
+        alt, delimited, map, number, pair, parse_line, preceded, separated1, space0, space1, tag,
+        Parser,
+    },
+    ParseError, Solution,
+};
 use std::convert::TryFrom;
 
+use crate::Day02;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Games {
     games: Vec<Game>,
 }
 
 impl TryFrom<&str> for Games {
-    type Error = anyhow::Error;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         let games = s
             .lines()
-            .map(Game::try_from)
+            .enumerate()
+            .map(|(i, line)| Game::try_from(line).map_err(|e| e.line(i + 1)))
             .collect::<Result<Vec<Game>, Self::Error>>()?;
         Ok(Self { games })
     }
@@ -45,23 +54,23 @@ pub struct Game {
 }
 
 impl TryFrom<&str> for Game {
-    type Error = anyhow::Error;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let re = Regex::new(r"Game (\d+): (.*)")?;
-        let caps = re.captures(s).ok_or(anyhow::anyhow!("Failed parsing."))?;
-
-        let id = caps[1].parse().unwrap();
-        let sets = caps[2]
-            .split(';')
-            .map(Set::try_from)
-            .collect::<Result<Vec<Set>, Self::Error>>()?;
-
-        Ok(Self { id, sets })
+        parse_line(Day02::DAY, s, Self::parser())
     }
 }
 
 impl Game {
+    /// `Game <id>: <set>; <set>; ...`
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let id = delimited(pair(tag("Game"), space1()), number(), tag(":"));
+        map(
+            pair(id, separated1(Set::parser(), tag(";"))),
+            |(id, sets)| Self { id, sets },
+        )
+    }
+
     fn is_possible(&self, max_set: &Set) -> bool {
         self.sets.iter().all(|s| s.is_possible(max_set))
     }
@@ -87,30 +96,53 @@ pub struct Set {
 }
 
 impl TryFrom<&str> for Set {
-    type Error = anyhow::Error;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let re_green = Regex::new(r"(\d+) green")?;
-        let re_blue = Regex::new(r"(\d+) blue")?;
-        let re_red = Regex::new(r"(\d+) red")?;
-
-        let red = match re_red.captures(s) {
-            Some(caps) => caps[1].parse().unwrap(),
-            None => 0,
-        };
-        let blue = match re_blue.captures(s) {
-            Some(caps) => caps[1].parse().unwrap(),
-            None => 0,
-        };
-        let green = match re_green.captures(s) {
-            Some(caps) => caps[1].parse().unwrap(),
-            None => 0,
-        };
-        Ok(Set { red, blue, green })
+        parse_line(Day02::DAY, s, Self::parser())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Colour {
+    Red,
+    Blue,
+    Green,
+}
+
+impl Colour {
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        alt(
+            alt(
+                map(tag("red"), |_| Self::Red),
+                map(tag("blue"), |_| Self::Blue),
+            ),
+            map(tag("green"), |_| Self::Green),
+        )
     }
 }
 
 impl Set {
+    /// `<n> <colour>, <n> <colour>, ...`
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let cube = delimited(
+            space0(),
+            pair(number(), preceded(space1(), Colour::parser())),
+            space0(),
+        );
+        map(separated1(cube, tag(",")), |cubes| {
+            let mut set = Set::default();
+            for (n, colour) in cubes {
+                match colour {
+                    Colour::Red => set.red = n,
+                    Colour::Blue => set.blue = n,
+                    Colour::Green => set.green = n,
+                }
+            }
+            set
+        })
+    }
+
     fn is_possible(&self, max_set: &Self) -> bool {
         self.red <= max_set.red && self.blue <= max_set.blue && self.green <= max_set.green
     }
@@ -234,4 +266,24 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
             }
         )
     }
+
+    #[test]
+    fn parse_error_position() {
+        let s = "Game 1: 3 blue, 4 red
+Game 2: 1 blue; 99999999999999999999 red, 2 green";
+        let e = Games::try_from(s).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.snippet.as_str()),
+            (2, 17, "99999999999999999999")
+        );
+
+        let e = Games::try_from("Game one: 3 blue").unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (1, 6, "a number"));
+
+        let e = Games::try_from("Game 1: 3 blue; 4 yellow").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 2, line 1, column 19: expected `red` or `blue` or `green`, found `yellow`"
+        );
+    }
 }
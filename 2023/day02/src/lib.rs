@@ -0,0 +1,37 @@
+use aoc_core::{Day, Result, Solution};
+
+pub use games::{Games, Set};
+
+mod games;
+
+pub const DAY: Day = Day::new::<Day02>();
+
+pub struct Day02(Games);
+
+impl Day02 {
+    const MAX_SET: Set = Set {
+        red: 12,
+        green: 13,
+        blue: 14,
+    };
+}
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self(Games::try_from(input)?))
+    }
+
+    fn part1(&self) -> Result<u64> {
+        let games = self.0.clone().filter_possible(&Self::MAX_SET);
+        Ok(games.id_sum())
+    }
+
+    fn part2(&self) -> Result<u64> {
+        Ok(self.0.power_sum_of_min_sets())
+    }
+}
@@ -1,9 +1,18 @@
+use aoc_core::{ParseError, Solution};
 use regex::Regex;
-use std::ops::Range;
+use std::{ops::Range, sync::LazyLock};
+
+use crate::Day03;
+
+static SYMBOL: LazyLock<Regex> = LazyLock::new(|| Regex::new(Engine::SYMBOL_REGEX).unwrap());
+static NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(Engine::NUMBER_REGEX).unwrap());
+static GEAR: LazyLock<Regex> = LazyLock::new(|| Regex::new(Engine::GEAR_REGEX).unwrap());
 
 #[derive(Debug, Clone)]
 pub struct Engine {
     schematic: String,
+    /// The numbers of each line of the schematic.
+    numbers: Vec<Vec<Number>>,
 }
 
 impl Engine {
@@ -11,8 +20,14 @@ impl Engine {
     const NUMBER_REGEX: &'static str = r"\d+";
     const GEAR_REGEX: &'static str = r"\*";
 
-    pub fn new(schematic: String) -> Engine {
-        Self { schematic }
+    /// Fails on a number too large for `u64`.
+    pub fn new(schematic: String) -> Result<Engine, ParseError> {
+        let numbers = schematic
+            .lines()
+            .enumerate()
+            .map(|(i, line)| Self::numbers(line).map_err(|e| e.line(i + 1)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { schematic, numbers })
     }
 
     pub fn part_numbers_sum(&self) -> u64 {
@@ -24,11 +39,11 @@ impl Engine {
         let mut last_matches: Vec<Range<usize>> = Vec::new();
         let mut last_possible_numbers: Vec<Number> = Vec::new();
 
-        for line in self.schematic.lines() {
+        for (line, numbers) in self.schematic.lines().zip(&self.numbers) {
             let matches = Self::symbol_matches(line);
             let mut possible_numbers = Vec::new();
 
-            for n in Self::numbers(line) {
+            for n in numbers.iter().cloned() {
                 match n.is_adjacent_list(&matches) {
                     true => final_numbers.push(n.number),
                     false => possible_numbers.push(n),
@@ -57,14 +72,22 @@ impl Engine {
     }
 
     fn symbol_matches(line: &str) -> Vec<Range<usize>> {
-        let re = Regex::new(Self::SYMBOL_REGEX).unwrap();
-        re.find_iter(line).map(|m| m.range()).collect()
+        SYMBOL.find_iter(line).map(|m| m.range()).collect()
     }
 
-    fn numbers(line: &str) -> Vec<Number> {
-        let re = Regex::new(Self::NUMBER_REGEX).unwrap();
-        re.find_iter(line)
-            .map(|m| Number::new(m.as_str().parse().expect("Regex matched digits"), m.range()))
+    fn numbers(line: &str) -> Result<Vec<Number>, ParseError> {
+        NUMBER
+            .find_iter(line)
+            .map(|m| match m.as_str().parse() {
+                Ok(n) => Ok(Number::new(n, m.range())),
+                Err(_) => Err(ParseError::new(
+                    Day03::DAY,
+                    line,
+                    m.start(),
+                    m.len(),
+                    "a number that fits",
+                )),
+            })
             .collect()
     }
 
@@ -74,14 +97,13 @@ impl Engine {
 
     fn gears(&self) -> Vec<Gear> {
         let mut final_gears = Vec::new();
-        let mut last_numbers: Vec<Number> = Vec::new();
+        let mut last_numbers: &[Number] = &[];
         let mut last_possible_gears: Vec<PossibleGear> = Vec::new();
 
-        for line in self.schematic.lines() {
-            let numbers = Self::numbers(line);
+        for (line, numbers) in self.schematic.lines().zip(&self.numbers) {
             let mut possible_gears = Self::possible_gears(line);
 
-            for n in &numbers {
+            for n in numbers {
                 for g in &mut possible_gears {
                     if g.is_adjacent(n) {
                         g.adjacents.push(n.number);
@@ -97,7 +119,7 @@ impl Engine {
             final_gears.extend(last_possible_gears);
 
             for g in &mut possible_gears {
-                for n in &last_numbers {
+                for n in last_numbers {
                     if g.is_adjacent(n) {
                         g.adjacents.push(n.number);
                     }
@@ -115,8 +137,7 @@ impl Engine {
     }
 
     fn possible_gears(line: &str) -> Vec<PossibleGear> {
-        let re = Regex::new(Self::GEAR_REGEX).unwrap();
-        re.find_iter(line)
+        GEAR.find_iter(line)
             .map(|m| PossibleGear::new(m.start()))
             .collect()
     }
@@ -218,7 +239,7 @@ mod tests {
 ......755.
 ...$.*....
 .664.598..";
-        Engine::new(s.to_string())
+        Engine::new(s.to_string()).unwrap()
     }
 
     #[test]
@@ -244,6 +265,16 @@ mod tests {
         assert_eq!(matches, vec![6..7, 9..11, 14..15, 21..22, 24..25, 27..28]);
     }
 
+    #[test]
+    fn number_too_large() {
+        let s = "467..114..\n...*......\n..35..99999999999999999999*";
+        let e = Engine::new(s.to_string()).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 3, line 3, column 7: expected a number that fits, found `99999999999999999999`"
+        );
+    }
+
     #[test]
     fn gear_ratio_sum() {
         let e = test_engine();
@@ -0,0 +1,28 @@
+use aoc_core::{Day, Result, Solution};
+
+pub use engine::Engine;
+
+mod engine;
+
+pub const DAY: Day = Day::new::<Day03>();
+
+pub struct Day03(Engine);
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self(Engine::new(input.to_string())?))
+    }
+
+    fn part1(&self) -> Result<u64> {
+        Ok(self.0.part_numbers_sum())
+    }
+
+    fn part2(&self) -> Result<u64> {
+        Ok(self.0.gear_ratio_sum())
+    }
+}
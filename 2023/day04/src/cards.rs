@@ -1,18 +1,27 @@
 use std::convert::TryFrom;
 
-use regex::Regex;
+use aoc_core::{
+    parse::{
+        delimited, many0, map, number, pair, parse_line, preceded, space0, space1, tag, terminated,
+        Parser,
+    },
+    ParseError, Solution,
+};
 
-#[derive(Debug)]
+use crate::Day04;
+
+#[derive(Debug, Clone)]
 pub struct Cards(Vec<CardCopies>);
 
 impl TryFrom<&str> for Cards {
-    type Error = anyhow::Error;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
         let copies = s
             .lines()
-            .filter(|s| !s.is_empty())
-            .map(Card::try_from)
+            .enumerate()
+            .filter(|(_, s)| !s.is_empty())
+            .map(|(i, s)| Card::try_from(s).map_err(|e| e.line(i + 1)))
             .map(|c| c.map(CardCopies::new))
             .collect::<Result<Vec<CardCopies>, Self::Error>>()?;
         Ok(Self(copies))
@@ -43,7 +52,7 @@ impl Cards {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct CardCopies {
     card: Card,
     copies: u64,
@@ -55,7 +64,7 @@ impl CardCopies {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Card {
     id: u64,
     winning: Vec<u64>,
@@ -63,13 +72,18 @@ pub struct Card {
 }
 
 impl Card {
-    const REGEX: &'static str = r"Card\s+(\d+): ([\d ]*)\|([\d ]*)";
-
-    fn collect_str(s: &str) -> Vec<u64> {
-        s.split(' ')
-            .filter(|s| !s.is_empty())
-            .map(|d| d.parse().unwrap())
-            .collect()
+    /// `Card <id>: <numbers> | <numbers>`
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let id = delimited(pair(tag("Card"), space1()), number(), tag(":"));
+        let numbers = || terminated(many0(preceded(space1(), number())), space0());
+        map(
+            pair(id, pair(terminated(numbers(), tag("|")), numbers())),
+            |(id, (winning, selected))| Self {
+                id,
+                winning,
+                selected,
+            },
+        )
     }
 
     fn matching(&self) -> usize {
@@ -89,23 +103,10 @@ impl Card {
 }
 
 impl TryFrom<&str> for Card {
-    type Error = anyhow::Error;
+    type Error = ParseError;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let re = Regex::new(Self::REGEX).unwrap();
-        let captures = re.captures(s).ok_or(anyhow::anyhow!("Regex failed"))?;
-        let id = captures.get(1).unwrap().as_str().parse()?;
-        let winning_str = captures.get(2).unwrap().as_str();
-        let selected_str = captures.get(3).unwrap().as_str();
-
-        let winning = Self::collect_str(winning_str);
-        let selected = Self::collect_str(selected_str);
-
-        Ok(Self {
-            id,
-            winning,
-            selected,
-        })
+        parse_line(Day04::DAY, s, Self::parser())
     }
 }
 
@@ -155,4 +156,22 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"
         cards.process();
         assert_eq!(30, cards.count());
     }
+
+    #[test]
+    fn parse_error_position() {
+        let s = "Card 1: 41 48 | 83 86
+
+Card 2: 13 32 | 61 99999999999999999999";
+        let e = Cards::try_from(s).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.snippet.as_str()),
+            (3, 20, "99999999999999999999")
+        );
+
+        let e = Cards::try_from("Card 1: 41 48 83 86 17 ; 83 86").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 4, line 1, column 24: expected `|`, found `;`"
+        );
+    }
 }
@@ -0,0 +1,30 @@
+use aoc_core::{Day, Result, Solution};
+
+pub use cards::Cards;
+
+mod cards;
+
+pub const DAY: Day = Day::new::<Day04>();
+
+pub struct Day04(Cards);
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self(Cards::try_from(input)?))
+    }
+
+    fn part1(&self) -> Result<u64> {
+        Ok(self.0.points())
+    }
+
+    fn part2(&self) -> Result<u64> {
+        let mut cards = self.0.clone();
+        cards.process();
+        Ok(cards.count())
+    }
+}
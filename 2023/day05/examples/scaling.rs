@@ -0,0 +1,52 @@
+//! Times brute-force part 2 on a generated almanac with 1, 2, 4, ... threads,
+//! up to every core:
+//!
+//! ```text
+//! cargo run --release -p day05 --features parallel --example scaling [SEED_RANGE_LEN]
+//! ```
+
+use std::time::{Duration, Instant};
+
+use day05::{Almanac, Generator, Kind};
+
+fn main() -> anyhow::Result<()> {
+    let seed_range_len = match std::env::args().nth(1) {
+        Some(len) => len.parse()?,
+        None => 1_000_000,
+    };
+    let input = Generator::new(2023)
+        .seed_ranges(10)
+        .seed_range_len(seed_range_len)
+        .generate();
+    let almanac = Almanac::from2(&input)?;
+
+    let cores = std::thread::available_parallelism()?.get();
+    let mut threads: Vec<usize> = std::iter::successors(Some(1), |n| Some(n * 2))
+        .take_while(|n| *n < cores)
+        .collect();
+    threads.push(cores);
+
+    let mut single: Option<Duration> = None;
+    for threads in threads {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()?;
+        let mut almanac = almanac.clone();
+        let start = Instant::now();
+        let smallest = pool.install(|| {
+            almanac.process_until(&Kind::LOCATION)?;
+            anyhow::Ok(almanac.smallest_number())
+        })?;
+        let time = start.elapsed();
+
+        let single = *single.get_or_insert(time);
+        println!(
+            "{:>3} threads: {:>10.3?} {:>6.2}x  smallest {:?}",
+            threads,
+            time,
+            single.as_secs_f64() / time.as_secs_f64(),
+            smallest
+        );
+    }
+    Ok(())
+}
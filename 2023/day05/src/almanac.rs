@@ -1,118 +1,413 @@
-use regex::Regex;
+use aoc_core::{
+    parse::{
+        alt, cut, many0, map, number, pair, parse_line, preceded, space0, space1, tag, take_while1,
+        terminated, Failure, Input, Parser,
+    },
+    ParseError, Solution,
+};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+    fmt,
+    ops::Range,
+};
+
+use crate::{Day05, Piecewise};
+
+pub use trace::{Step, Trace};
+pub use validate::Diagnostic;
+
+#[cfg(test)]
+mod differential;
+mod trace;
+mod validate;
+
+/// Source and destination category of one map section.
+type Stage = (Kind, Kind);
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Almanac {
     values: Vec<Value>,
+    sections: Sections,
+}
+
+/// What an almanac was parsed from: the numbers of the seeds line as
+/// written, and the map sections, each stage once with all its rows.
+#[derive(Debug, PartialEq, Clone)]
+struct Sections {
+    seeds: Vec<u64>,
+    stages: Vec<Stage>,
     maps: Vec<Map>,
 }
 
-impl Almanac {
-    const SEEDS_REGEX: &'static str = r"seeds: ([\d ]+)";
-    const MAP_REGEX: &'static str = r"^(\d+) (\d+) (\d+)$";
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AlmanacError {
+    #[error("unknown category `{0}`")]
+    UnknownCategory(Kind),
 
-    pub fn from1(s: &str) -> anyhow::Result<Self> {
+    #[error("no maps lead from `{from}` to `{to}`")]
+    Disconnected { from: Kind, to: Kind },
+
+    #[error("no maps lead out of `{0}`")]
+    Stuck(Kind),
+
+    #[error("maps loop back to `{0}`")]
+    Cycle(Kind),
+
+    #[error("line {line}: map row ends past u64::MAX")]
+    Overflow { line: usize },
+}
+
+impl Almanac {
+    pub fn from1(s: &str) -> Result<Self, ParseError> {
         let numbers = Self::parse_seed_numbers(s)?;
-        let values = Self::parse_values1(numbers);
-        let maps = Self::parse_maps(s);
-        Ok(Self { values, maps })
+        let values = Self::parse_values1(numbers.clone());
+        Ok(Self {
+            values,
+            sections: Sections::parse(s, numbers)?,
+        })
     }
 
-    pub fn from2(s: &str) -> anyhow::Result<Self> {
-        let numbers = Self::parse_seed_numbers(s)?;
-        let values = Self::parse_values2(numbers);
-        let maps = Self::parse_maps(s);
-        Ok(Self { values, maps })
+    /// Brute-force part 2, expanding every seed range into individual values.
+    /// Kept as a reference for [`RangeAlmanac`], which is what part 2 uses.
+    pub fn from2(s: &str) -> Result<Self, ParseError> {
+        let ranges = Self::parse_seed_ranges(s)?;
+        let numbers = Self::range_numbers(&ranges);
+        let values = Self::parse_values2(ranges);
+        Ok(Self {
+            values,
+            sections: Sections::parse(s, numbers)?,
+        })
     }
 
-    fn parse_seed_numbers(s: &str) -> anyhow::Result<Vec<u64>> {
-        let re = Regex::new(Self::SEEDS_REGEX).unwrap();
-        let captures = re.captures(s).ok_or(anyhow::anyhow!("Regex failed"))?;
-        let numbers = captures.get(1).unwrap().as_str();
-        Ok(numbers
-            .split_whitespace()
-            .filter(|s| !s.is_empty())
-            .map(|s| s.parse().unwrap())
-            .collect::<Vec<u64>>())
+    /// Non-empty lines with their 1-based line numbers.
+    fn lines(s: &str) -> impl Iterator<Item = (usize, &str)> {
+        s.lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| !line.is_empty())
+    }
+
+    /// `seeds: <number> <number> ...`, the first line.
+    fn parse_seed_numbers(s: &str) -> Result<Vec<u64>, ParseError> {
+        let (i, line) = Self::lines(s).next().unwrap_or((1, ""));
+        let seeds = preceded(tag("seeds:"), many0(preceded(space1(), number())));
+        parse_line(Day05::DAY, line, terminated(seeds, space0())).map_err(|e| e.line(i))
     }
 
     fn parse_values1(numbers: Vec<u64>) -> Vec<Value> {
         numbers
             .into_iter()
             .map(|v| Value {
-                kind: Kind::Seed,
+                kind: Kind::SEED,
+                value: v,
+            })
+            .collect()
+    }
+
+    /// `seeds: <start> <length> ...`, the first line read as ranges.
+    fn parse_seed_ranges(s: &str) -> Result<Vec<Range<u64>>, ParseError> {
+        let (i, line) = Self::lines(s).next().unwrap_or((1, ""));
+        let seeds = preceded(tag("seeds:"), many0(seed_range()));
+        parse_line(Day05::DAY, line, terminated(seeds, space0())).map_err(|e| e.line(i))
+    }
+
+    /// The seeds line of `ranges` again, a start and a length for each.
+    fn range_numbers(ranges: &[Range<u64>]) -> Vec<u64> {
+        ranges
+            .iter()
+            .flat_map(|r| [r.start, r.end - r.start])
+            .collect()
+    }
+
+    fn parse_values2(ranges: Vec<Range<u64>>) -> Vec<Value> {
+        ranges
+            .into_iter()
+            .flatten()
+            .map(|v| Value {
                 value: v,
+                kind: Kind::SEED,
+            })
+            .collect()
+    }
+
+    fn parse_ranges2(ranges: Vec<Range<u64>>) -> Vec<ValueRange> {
+        ranges
+            .into_iter()
+            .map(|range| ValueRange {
+                kind: Kind::SEED,
+                range,
             })
             .collect()
     }
 
-    fn parse_values2(numbers: Vec<u64>) -> Vec<Value> {
-        let mut values = Vec::new();
-        for chunk in numbers.chunks_exact(2) {
-            let start = chunk[0];
-            let length = chunk[1];
-            for v in start..(start + length) {
-                values.push(Value {
-                    value: v,
-                    kind: Kind::Seed,
-                })
+    /// Converts `value` from the `from` category to the `to` one, through
+    /// the shortest chain of maps between them.
+    pub fn convert(&self, from: &str, to: &str, value: u64) -> Result<u64, AlmanacError> {
+        let path = self.path(&Kind::new(from), &Kind::new(to))?;
+        Ok(path.iter().fold(value, |value, stage| {
+            self.row(stage, value).map_or(value, |m| m.map(value))
+        }))
+    }
+
+    /// The row of `stage` that maps `value`, the first one if several do.
+    fn row<'a>(&'a self, stage: &'a Stage, value: u64) -> Option<&'a Map> {
+        self.sections
+            .rows(stage)
+            .find(|m| m.source_range().contains(&value))
+    }
+
+    /// The chain of maps from `from` to `to` as a single function, with
+    /// each lookup a binary search instead of a pass over every stage.
+    pub fn compose(&self, from: &str, to: &str) -> Result<Piecewise, AlmanacError> {
+        let path = self.path(&Kind::new(from), &Kind::new(to))?;
+        Ok(path
+            .iter()
+            .fold(Piecewise::identity(), |f, stage| f.then(&self.stage(stage))))
+    }
+
+    fn stage(&self, stage: &Stage) -> Piecewise {
+        let rows: Vec<(Range<u64>, u64)> = self
+            .sections
+            .rows(stage)
+            .map(|m| (m.source_range(), m.dest_start))
+            .collect();
+        Piecewise::stage(&rows)
+    }
+
+    /// Stages leading from `from` to `to`, found breadth first.
+    fn path(&self, from: &Kind, to: &Kind) -> Result<Vec<&Stage>, AlmanacError> {
+        self.sections.route(from, to).map_err(|e| match e {
+            AlmanacError::Stuck(_) | AlmanacError::Cycle(_) => AlmanacError::Disconnected {
+                from: from.clone(),
+                to: to.clone(),
+            },
+            e => e,
+        })
+    }
+
+    /// Processes the values until they are all `target`s.
+    /// Without any values, `target` must still be reachable from `seed`.
+    pub fn process_until(&mut self, target: &Kind) -> Result<(), AlmanacError> {
+        let kind = self.values.first().map_or(Kind::SEED, |v| v.kind.clone());
+        for stage in self.sections.route(&kind, target)? {
+            let rows: Vec<&Map> = self.sections.rows(stage).collect();
+            for_each(&mut self.values, |v| v.map(&rows, &stage.1));
+        }
+        Ok(())
+    }
+
+    pub fn smallest_number(&self) -> Option<u64> {
+        #[cfg(feature = "parallel")]
+        let values = self.values.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let values = self.values.iter();
+        values.map(|v| v.value).min()
+    }
+}
+
+/// Runs `f` on every value, spread over all cores with the `parallel` feature.
+fn for_each(values: &mut [Value], f: impl Fn(&mut Value) + Send + Sync) {
+    #[cfg(feature = "parallel")]
+    values.par_iter_mut().for_each(f);
+    #[cfg(not(feature = "parallel"))]
+    values.iter_mut().for_each(f);
+}
+
+/// Writes the almanac back in the puzzle's format as it was parsed, even
+/// after its values were processed.
+impl fmt::Display for Almanac {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sections)
+    }
+}
+
+/// The `seeds:` line, then every map section in the order their headers
+/// first appeared. That order is the canonical one: the categories need
+/// not form a single chain to sort them along, and it keeps a hand-written
+/// almanac's layout.
+impl fmt::Display for Sections {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "seeds:")?;
+        for n in &self.seeds {
+            write!(f, " {}", n)?;
+        }
+        writeln!(f)?;
+
+        for stage in &self.stages {
+            write!(f, "\n{}-to-{} map:\n", stage.0, stage.1)?;
+            for m in self.rows(stage) {
+                writeln!(f, "{} {} {}", m.dest_start, m.source_start, m.length)?;
             }
         }
-        values
-    }
-
-    fn parse_maps(s: &str) -> Vec<Map> {
-        let mut maps = Vec::new();
-        let map_re = Regex::new(Self::MAP_REGEX).unwrap();
-        let (mut from, mut to) = (Kind::Seed, Kind::Seed);
-
-        for line in s.lines().filter(|s| !s.is_empty()) {
-            (from, to) = match line.trim() {
-                "seed-to-soil map:" => (Kind::Seed, Kind::Soil),
-                "soil-to-fertilizer map:" => (Kind::Soil, Kind::Fertilizer),
-                "fertilizer-to-water map:" => (Kind::Fertilizer, Kind::Water),
-                "water-to-light map:" => (Kind::Water, Kind::Light),
-                "light-to-temperature map:" => (Kind::Light, Kind::Temperature),
-                "temperature-to-humidity map:" => (Kind::Temperature, Kind::Humidity),
-                "humidity-to-location map:" => (Kind::Humidity, Kind::Location),
-                _ => {
-                    let captures = map_re.captures(line);
-                    let captures = match captures {
-                        Some(captures) => captures,
-                        None => continue,
-                    };
-                    let dest_start = captures.get(1).unwrap().as_str().parse().unwrap();
-                    let source_start = captures.get(2).unwrap().as_str().parse().unwrap();
-                    let length = captures.get(3).unwrap().as_str().parse().unwrap();
+        Ok(())
+    }
+}
+
+/// Part 2 almanac, carrying whole seed ranges through each stage
+/// instead of individual values, so it never expands them.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RangeAlmanac {
+    ranges: Vec<ValueRange>,
+    sections: Sections,
+}
+
+impl RangeAlmanac {
+    pub fn from2(s: &str) -> Result<Self, ParseError> {
+        let ranges = Almanac::parse_seed_ranges(s)?;
+        let numbers = Almanac::range_numbers(&ranges);
+        let ranges = Almanac::parse_ranges2(ranges);
+        Ok(Self {
+            ranges,
+            sections: Sections::parse(s, numbers)?,
+        })
+    }
+
+    /// Processes the ranges until they are all `target`s.
+    /// Without any ranges, `target` must still be reachable from `seed`.
+    pub fn process_until(&mut self, target: &Kind) -> Result<(), AlmanacError> {
+        let kind = self.ranges.first().map_or(Kind::SEED, |r| r.kind.clone());
+        for stage in self.sections.route(&kind, target)? {
+            let rows: Vec<&Map> = self.sections.rows(stage).collect();
+            self.ranges = std::mem::take(&mut self.ranges)
+                .into_iter()
+                .flat_map(|r| r.map(&rows, &stage.1))
+                .collect();
+        }
+        Ok(())
+    }
 
+    pub fn smallest_number(&self) -> Option<u64> {
+        self.ranges
+            .iter()
+            .filter(|r| !r.range.is_empty())
+            .map(|r| r.range.start)
+            .min()
+    }
+}
+
+/// Writes the almanac back in the puzzle's format as it was parsed, seed
+/// ranges and all.
+impl fmt::Display for RangeAlmanac {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sections)
+    }
+}
+
+impl Sections {
+    /// Every `<kind>-to-<kind> map:` header following the seeds line,
+    /// and the map rows under them. A repeated header adds its rows to the
+    /// first section with that name, so each stage appears once. `seeds` are
+    /// the numbers already parsed from the seeds line.
+    fn parse(s: &str, seeds: Vec<u64>) -> Result<Self, ParseError> {
+        let mut stages: Vec<Stage> = Vec::new();
+        let mut maps: Vec<Map> = Vec::new();
+        let mut section: Option<Stage> = None;
+
+        for (i, line) in Almanac::lines(s).skip(1) {
+            match parse_line(Day05::DAY, line, Line::parser()).map_err(|e| e.line(i))? {
+                Line::Header(from, to) => {
+                    let stage = (from, to);
+                    if !stages.contains(&stage) {
+                        stages.push(stage.clone());
+                    }
+                    section = Some(stage);
+                }
+                Line::Row(dest_start, source_start, length) => {
+                    let (from, to) = section.clone().ok_or_else(|| {
+                        ParseError::new(Day05::DAY, line, 0, line.len(), "a map header").line(i)
+                    })?;
                     maps.push(Map {
                         dest_start,
                         source_start,
                         length,
                         from,
                         to,
+                        line: i,
                     });
-
-                    (from, to)
                 }
-            };
+            }
         }
-        maps
+        maps.sort_by_key(|m| stages.iter().position(|(f, t)| f == &m.from && t == &m.to));
+        Ok(Self {
+            seeds,
+            stages,
+            maps,
+        })
     }
 
-    pub fn process(&mut self) {
-        for v in &mut self.values {
-            v.process(&self.maps);
-        }
+    /// The rows of `stage`, in input order.
+    fn rows<'a>(&'a self, (from, to): &'a Stage) -> impl Iterator<Item = &'a Map> + 'a {
+        self.maps
+            .iter()
+            .filter(move |m| &m.from == from && &m.to == to)
     }
 
-    pub fn process_times(&mut self, times: usize) {
-        for i in 0..times {
-            self.process()
+    /// The shortest chain of stages taking `from` to `to`, refused if one
+    /// of its rows ends past `u64::MAX`, so following it never overflows.
+    fn route(&self, from: &Kind, to: &Kind) -> Result<Vec<&Stage>, AlmanacError> {
+        let route = self.search(from, to)?;
+        match route
+            .iter()
+            .flat_map(|stage| self.rows(stage))
+            .find(|m| !m.fits())
+        {
+            Some(m) => Err(AlmanacError::Overflow { line: m.line }),
+            None => Ok(route),
         }
     }
 
-    pub fn smallest_number(&self) -> Option<u64> {
-        self.values.iter().map(|v| v.value).min()
+    /// The shortest chain of stages taking `from` to `to`, found breadth first.
+    ///
+    /// When `to` can't be reached, the error comes from following the first map
+    /// section out of each category instead: the category it gets stuck in, or
+    /// the one it loops back to.
+    fn search(&self, from: &Kind, to: &Kind) -> Result<Vec<&Stage>, AlmanacError> {
+        let stages = &self.stages;
+        if from == to {
+            return Ok(Vec::new());
+        }
+        for kind in [from, to] {
+            if !stages.iter().any(|(f, t)| f == kind || t == kind) {
+                return Err(AlmanacError::UnknownCategory(kind.clone()));
+            }
+        }
+
+        let mut previous: HashMap<&Kind, &Stage> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(kind) = queue.pop_front() {
+            if kind == to {
+                let mut route = Vec::new();
+                let mut kind = to;
+                while let Some(stage) = previous.get(kind) {
+                    route.push(*stage);
+                    kind = &stage.0;
+                }
+                route.reverse();
+                return Ok(route);
+            }
+            for stage in stages.iter().filter(|(f, _)| f == kind) {
+                if stage.1 != *from && !previous.contains_key(&stage.1) {
+                    previous.insert(&stage.1, stage);
+                    queue.push_back(&stage.1);
+                }
+            }
+        }
+
+        let mut walked = vec![from];
+        loop {
+            let kind = walked[walked.len() - 1];
+            let (_, next) = stages
+                .iter()
+                .find(|(f, _)| f == kind)
+                .ok_or_else(|| AlmanacError::Stuck(kind.clone()))?;
+            if walked.contains(&next) {
+                return Err(AlmanacError::Cycle(next.clone()));
+            }
+            walked.push(next);
+        }
     }
 }
 
@@ -123,47 +418,193 @@ struct Value {
 }
 
 impl Value {
-    fn process(&mut self, maps: &[Map]) {
-        let maps: Vec<&Map> = maps.iter().filter(|m| m.from == self.kind).collect();
-        if !maps.is_empty() {
-            self.kind = maps[0].to;
-            for m in maps {
-                if (m.source_start..(m.source_start + m.length)).contains(&self.value) {
-                    self.value += m.dest_start;
-                    self.value -= m.source_start;
-                    break;
+    /// Maps the value with the first row containing it, making it a `to`.
+    fn map(&mut self, rows: &[&Map], to: &Kind) {
+        self.kind = to.clone();
+        if let Some(m) = rows.iter().find(|m| m.source_range().contains(&self.value)) {
+            self.value = m.map(self.value);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct ValueRange {
+    kind: Kind,
+    range: Range<u64>,
+}
+
+impl ValueRange {
+    /// Splits the range against `rows`, mapping each intersecting piece and
+    /// passing the leftovers through unchanged, all of them as `to`s.
+    fn map(self, rows: &[&Map], to: &Kind) -> Vec<ValueRange> {
+        let kind = to.clone();
+        let mut mapped = Vec::new();
+        let mut unmapped = vec![self.range];
+
+        for m in rows {
+            let source = m.source_range();
+            let mut rest = Vec::new();
+
+            for r in unmapped {
+                let start = r.start.max(source.start);
+                let end = r.end.min(source.end);
+                if start >= end {
+                    rest.push(r);
+                    continue;
+                }
+
+                mapped.push(ValueRange {
+                    kind: kind.clone(),
+                    range: m.map(start)..m.map(end),
+                });
+                if r.start < start {
+                    rest.push(r.start..start);
+                }
+                if end < r.end {
+                    rest.push(end..r.end);
                 }
             }
+
+            unmapped = rest;
         }
+
+        mapped.extend(unmapped.into_iter().map(|range| ValueRange {
+            kind: kind.clone(),
+            range,
+        }));
+        mapped
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum Kind {
-    Seed,
-    Soil,
-    Fertilizer,
-    Water,
-    Light,
-    Temperature,
-    Humidity,
-    Location,
+/// Category of a value, named by the almanac's map headers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Kind(Cow<'static, str>);
+
+impl Kind {
+    pub const SEED: Kind = Kind(Cow::Borrowed("seed"));
+    pub const LOCATION: Kind = Kind(Cow::Borrowed("location"));
+
+    pub fn new(name: &str) -> Self {
+        Self(Cow::Owned(name.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let name = take_while1(|c| c.is_ascii_alphanumeric() || c == '_', "a category");
+        map(name, Self::new)
+    }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// ` <start> <length>` on the seeds line. Once a start parsed its length
+/// must follow, and the range must end within `u64`.
+fn seed_range<'a>() -> impl Parser<'a, Range<u64>> {
+    move |input: Input<'a>| {
+        let (input, start) = preceded(space1(), number::<u64>())(input)?;
+        let (at, ()) = space0()(input)?;
+        let (rest, length) = number::<u64>()(at).map_err(|f| match f.cut {
+            true => f,
+            false => Failure {
+                expected: "a range length".into(),
+                cut: true,
+                ..f
+            },
+        })?;
+        match start.checked_add(length) {
+            Some(end) => Ok((rest, start..end)),
+            None => cut(|at: Input<'a>| at.fail("a range ending within u64"))(at),
+        }
+    }
+}
+
+/// A line of the maps section.
+enum Line {
+    Header(Kind, Kind),
+    Row(u64, u64, u64),
+}
+
+impl Line {
+    fn parser<'a>() -> impl Parser<'a, Self> {
+        let header = terminated(
+            pair(terminated(Kind::parser(), tag("-to-")), Kind::parser()),
+            pair(space1(), tag("map:")),
+        );
+        let row = pair(
+            number(),
+            pair(preceded(space1(), number()), preceded(space1(), number())),
+        );
+        terminated(
+            alt(
+                map(header, |(from, to)| Self::Header(from, to)),
+                map(row, |(dest, (source, length))| {
+                    Self::Row(dest, source, length)
+                }),
+            ),
+            space0(),
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
 struct Map {
     dest_start: u64,
     source_start: u64,
     length: u64,
     from: Kind,
     to: Kind,
+    /// 1-based line of the row in the input, not part of its identity.
+    line: usize,
+}
+
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        (
+            self.dest_start,
+            self.source_start,
+            self.length,
+            &self.from,
+            &self.to,
+        ) == (
+            other.dest_start,
+            other.source_start,
+            other.length,
+            &other.from,
+            &other.to,
+        )
+    }
+}
+
+impl Map {
+    /// Whether both the source and destination ranges end within `u64`.
+    fn fits(&self) -> bool {
+        self.source_start.checked_add(self.length).is_some()
+            && self.dest_start.checked_add(self.length).is_some()
+    }
+
+    fn source_range(&self) -> Range<u64> {
+        self.source_start..(self.source_start + self.length)
+    }
+
+    /// Maps a value in (or at the end of) the source range.
+    fn map(&self, value: u64) -> u64 {
+        value - self.source_start + self.dest_start
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
-    const TEST_INPUT: &'static str = "seeds: 79 14 55 13
+    pub(super) const TEST_INPUT: &str = "seeds: 79 14 55 13
 seed-to-soil map:
 50 98 2
 52 50 48
@@ -203,30 +644,367 @@ humidity-to-location map:
             almanac.values,
             vec![
                 Value {
-                    kind: Kind::Seed,
+                    kind: Kind::SEED,
                     value: 79
                 },
                 Value {
-                    kind: Kind::Seed,
+                    kind: Kind::SEED,
                     value: 14,
                 },
                 Value {
-                    kind: Kind::Seed,
+                    kind: Kind::SEED,
                     value: 55,
                 },
                 Value {
-                    kind: Kind::Seed,
+                    kind: Kind::SEED,
                     value: 13,
                 }
             ]
         );
-        assert_eq!(almanac.maps.len(), 18);
+        assert_eq!(almanac.sections.maps.len(), 18);
     }
 
     #[test]
     fn locations() {
         let mut almanac = Almanac::from1(TEST_INPUT).unwrap();
-        almanac.process_times(10);
+        almanac.process_until(&Kind::LOCATION).unwrap();
         assert_eq!(almanac.smallest_number().unwrap(), 35);
     }
+
+    #[test]
+    fn locations2() {
+        let mut almanac = RangeAlmanac::from2(TEST_INPUT).unwrap();
+        almanac.process_until(&Kind::LOCATION).unwrap();
+        assert_eq!(almanac.smallest_number().unwrap(), 46);
+    }
+
+    #[test]
+    fn locations2_brute_force() {
+        let mut almanac = Almanac::from2(TEST_INPUT).unwrap();
+        almanac.process_until(&Kind::LOCATION).unwrap();
+        assert_eq!(almanac.smallest_number().unwrap(), 46);
+    }
+
+    #[test]
+    fn parse_error_position() {
+        let e = Almanac::from1("seeds: 1 99999999999999999999 3").unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.snippet.as_str()),
+            (1, 10, "99999999999999999999")
+        );
+
+        let s = "seeds: 1
+
+seed-to-soil map:
+1 2 3
+1 99999999999999999999 3";
+        let e = Almanac::from1(s).unwrap_err();
+        assert_eq!((e.line, e.column), (5, 3));
+
+        let e = Almanac::from1("seed: 1").unwrap_err();
+        assert_eq!(e.expected, "`seeds:`");
+
+        let s = "seeds: 1
+seed-to-dirt-map:
+1 2 3";
+        let e = Almanac::from1(s).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 5, line 2, column 13: expected a space, found `-map:`"
+        );
+
+        let e = Almanac::from1("seeds: 1\n1 2 3").unwrap_err();
+        assert_eq!((e.line, e.expected.as_str()), (2, "a map header"));
+    }
+
+    #[test]
+    fn overflowing_rows() {
+        let s = "seeds: 1 2
+seed-to-soil map:
+0 18446744073709551615 2
+
+soil-to-location map:
+
+seed-to-dirt map:
+18446744073709551614 0 5";
+        let overflow = AlmanacError::Overflow { line: 3 };
+        let mut almanac = Almanac::from1(s).unwrap();
+        assert_eq!(almanac.validate()[0], Diagnostic::Overflow { line: 3 });
+        assert_eq!(almanac.convert("soil", "location", 1), Ok(1));
+        assert_eq!(
+            almanac.convert("seed", "location", 1),
+            Err(overflow.clone())
+        );
+        assert_eq!(almanac.compose("seed", "location").unwrap_err(), overflow);
+        assert_eq!(almanac.trace(1).unwrap_err(), overflow);
+        assert_eq!(
+            almanac.process_until(&Kind::new("dirt")),
+            Err(AlmanacError::Overflow { line: 8 })
+        );
+        assert_eq!(
+            almanac.process_until(&Kind::LOCATION),
+            Err(overflow.clone())
+        );
+        let mut ranges = RangeAlmanac::from2(s).unwrap();
+        assert_eq!(ranges.process_until(&Kind::LOCATION), Err(overflow));
+
+        let e = Day05::parse(s).unwrap().part2().unwrap_err();
+        assert_eq!(e.to_string(), "part 2 failed");
+    }
+
+    #[test]
+    fn seed_range_errors() {
+        let s = "seeds: 18446744073709551615 2\nseed-to-soil map:";
+        let e = RangeAlmanac::from2(s).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 5, line 1, column 29: expected a range ending within u64, found `2`"
+        );
+        assert_eq!(Almanac::from2(s).unwrap_err(), e);
+        assert!(Almanac::from1(s).is_ok());
+
+        let e = RangeAlmanac::from2("seeds: 1 2 3\nseed-to-soil map:").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "day 5, line 1, column 13: expected a range length, found end of line"
+        );
+
+        let ranges = RangeAlmanac::from2("seeds: 18446744073709551614 1 \n").unwrap();
+        assert_eq!(ranges.ranges[0].range, u64::MAX - 1..u64::MAX);
+    }
+
+    #[test]
+    fn convert() {
+        let almanac = Almanac::from1(TEST_INPUT).unwrap();
+        assert_eq!(almanac.convert("seed", "humidity", 79), Ok(78));
+        assert_eq!(almanac.convert("seed", "location", 79), Ok(82));
+        assert_eq!(almanac.convert("water", "light", 81), Ok(74));
+        assert_eq!(almanac.convert("soil", "soil", 7), Ok(7));
+
+        assert_eq!(
+            almanac.convert("seed", "dirt", 79),
+            Err(AlmanacError::UnknownCategory(Kind::new("dirt")))
+        );
+        assert_eq!(
+            almanac.convert("location", "seed", 82),
+            Err(AlmanacError::Disconnected {
+                from: Kind::LOCATION,
+                to: Kind::SEED
+            })
+        );
+    }
+
+    #[test]
+    fn arbitrary_categories() {
+        let s = "seeds: 1 5
+seed-to-dirt map:
+10 0 3
+
+dirt-to-location map:
+
+seed-to-rock map:
+0 5 1";
+        let almanac = Almanac::from1(s).unwrap();
+        assert_eq!(almanac.convert("seed", "location", 1), Ok(11));
+        assert_eq!(almanac.convert("seed", "rock", 5), Ok(0));
+        assert_eq!(
+            almanac.convert("rock", "location", 0),
+            Err(AlmanacError::Disconnected {
+                from: Kind::new("rock"),
+                to: Kind::LOCATION
+            })
+        );
+    }
+
+    #[test]
+    fn compose() {
+        let seeds: Vec<String> = (0..120).map(|seed| seed.to_string()).collect();
+        let s = TEST_INPUT.replacen("79 14 55 13", &seeds.join(" "), 1);
+        let mut almanac = Almanac::from1(&s).unwrap();
+        let location = almanac.compose("seed", "location").unwrap();
+        let seed = location.inverse().unwrap();
+
+        almanac.process_until(&Kind::LOCATION).unwrap();
+        for (i, v) in almanac.values.iter().enumerate() {
+            assert_eq!(location.apply(i as u64), v.value);
+            assert_eq!(seed.apply(v.value), i as u64);
+        }
+        assert_eq!(
+            almanac.compose("water", "light").unwrap().apply(81),
+            almanac.convert("water", "light", 81).unwrap()
+        );
+    }
+
+    #[test]
+    fn process_until_errors() {
+        let stuck = "seeds: 1
+seed-to-soil map:
+5 0 10
+
+location-to-seed map:";
+        let mut almanac = Almanac::from1(stuck).unwrap();
+        assert_eq!(
+            almanac.process_until(&Kind::LOCATION),
+            Err(AlmanacError::Stuck(Kind::new("soil")))
+        );
+        let mut ranges = RangeAlmanac::from2(&stuck.replacen("seeds: 1", "seeds: 1 2", 1)).unwrap();
+        assert_eq!(
+            ranges.process_until(&Kind::LOCATION),
+            Err(AlmanacError::Stuck(Kind::new("soil")))
+        );
+
+        let cycle = "seeds: 1
+seed-to-soil map:
+soil-to-water map:
+water-to-soil map:
+humidity-to-location map:";
+        let mut almanac = Almanac::from1(cycle).unwrap();
+        assert_eq!(
+            almanac.process_until(&Kind::LOCATION),
+            Err(AlmanacError::Cycle(Kind::new("soil")))
+        );
+
+        let mut almanac = Almanac::from1(TEST_INPUT).unwrap();
+        assert_eq!(
+            almanac.process_until(&Kind::new("dirt")),
+            Err(AlmanacError::UnknownCategory(Kind::new("dirt")))
+        );
+        assert_eq!(almanac.process_until(&Kind::new("water")), Ok(()));
+        assert_eq!(almanac.process_until(&Kind::new("water")), Ok(()));
+        assert_eq!(almanac.process_until(&Kind::LOCATION), Ok(()));
+        assert_eq!(almanac.smallest_number(), Some(35));
+
+        let empty = "seeds:\nseed-to-soil map:";
+        let mut almanac = Almanac::from1(empty).unwrap();
+        assert_eq!(
+            almanac.process_until(&Kind::new("nope")),
+            Err(AlmanacError::UnknownCategory(Kind::new("nope")))
+        );
+        assert_eq!(almanac.process_until(&Kind::new("soil")), Ok(()));
+        let mut ranges = RangeAlmanac::from2(empty).unwrap();
+        assert_eq!(
+            ranges.process_until(&Kind::new("nope")),
+            Err(AlmanacError::UnknownCategory(Kind::new("nope")))
+        );
+    }
+
+    #[test]
+    fn second_branch() {
+        let s = "seeds: 1 2
+seed-to-rock map:
+
+seed-to-soil map:
+5 0 10
+
+soil-to-location map:";
+        let mut almanac = Almanac::from1(s).unwrap();
+        assert_eq!(almanac.convert("seed", "location", 1), Ok(6));
+        almanac.process_until(&Kind::LOCATION).unwrap();
+        assert_eq!(almanac.smallest_number(), Some(6));
+
+        let mut ranges = RangeAlmanac::from2(s).unwrap();
+        ranges.process_until(&Kind::LOCATION).unwrap();
+        assert_eq!(ranges.smallest_number(), Some(6));
+
+        let s = "seeds: 1 5
+seed-to-dirt map:
+10 0 3
+
+dirt-to-location map:
+
+seed-to-rock map:
+0 5 1";
+        let mut almanac = Almanac::from1(s).unwrap();
+        almanac.process_until(&Kind::new("rock")).unwrap();
+        assert_eq!(almanac.smallest_number(), Some(0));
+    }
+
+    #[test]
+    fn display() {
+        let almanac = Almanac::from1(TEST_INPUT).unwrap();
+        let s = almanac.to_string();
+        assert!(s.starts_with("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\n"));
+        assert!(s.ends_with("humidity-to-location map:\n60 56 37\n56 93 4\n"));
+        assert_eq!(Almanac::from1(&s).unwrap(), almanac);
+
+        let s = "seeds:\n\nseed-to-soil map:\n\nsoil-to-location map:\n1 2 3\n";
+        assert_eq!(Almanac::from1(s).unwrap().to_string(), s);
+
+        let repeated = "seeds: 1
+seed-to-soil map:
+1 2 3
+soil-to-location map:
+4 5 6
+seed-to-soil map:
+7 8 9";
+        let almanac = Almanac::from1(repeated).unwrap();
+        assert_eq!(almanac.sections.stages.len(), 2);
+        assert_eq!(
+            almanac.to_string(),
+            "seeds: 1\n\nseed-to-soil map:\n1 2 3\n7 8 9\n\nsoil-to-location map:\n4 5 6\n"
+        );
+        assert_eq!(Almanac::from1(&almanac.to_string()).unwrap(), almanac);
+    }
+
+    #[test]
+    fn display_after_processing() {
+        let s = Almanac::from1(TEST_INPUT).unwrap().to_string();
+        let mut almanac = Almanac::from1(TEST_INPUT).unwrap();
+        almanac.process_until(&Kind::LOCATION).unwrap();
+        assert_eq!(almanac.to_string(), s);
+
+        let mut almanac = Almanac::from2(TEST_INPUT).unwrap();
+        assert_eq!(almanac.to_string(), s);
+        assert_eq!(Almanac::from2(&s).unwrap(), almanac);
+        almanac.process_until(&Kind::LOCATION).unwrap();
+        assert_eq!(almanac.to_string(), s);
+
+        let mut ranges = RangeAlmanac::from2(TEST_INPUT).unwrap();
+        assert_eq!(ranges.to_string(), s);
+        ranges.process_until(&Kind::LOCATION).unwrap();
+        assert_eq!(ranges.to_string(), s);
+    }
+
+    /// Inputs of random seed ranges and sections, single letter categories
+    /// making repeated headers common.
+    fn inputs() -> impl Strategy<Value = String> {
+        let kind = prop_oneof!["[a-c]", "[a-z_][a-z0-9_]{0,7}"];
+        let seed_range = any::<u64>().prop_flat_map(|start| (Just(start), 0..=u64::MAX - start));
+        let row = (any::<u64>(), any::<u64>(), any::<u64>());
+        let stage = (kind.clone(), kind, prop::collection::vec(row, 0..4));
+        (
+            prop::collection::vec(seed_range, 0..3),
+            prop::collection::vec(stage, 0..6),
+        )
+            .prop_map(|(seeds, sections)| {
+                let mut s = String::from("seeds:");
+                for (start, len) in seeds {
+                    s += &format!(" {} {}", start, len);
+                }
+                for (from, to, rows) in sections {
+                    s += &format!("\n{}-to-{} map:\n", from, to);
+                    for (dest_start, source_start, length) in rows {
+                        s += &format!("{} {} {}\n", dest_start, source_start, length);
+                    }
+                }
+                s
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trip(input in inputs()) {
+            let almanac = Almanac::from1(&input).unwrap();
+            let s = almanac.to_string();
+            let parsed = Almanac::from1(&s).unwrap();
+            prop_assert_eq!(&parsed, &almanac);
+            prop_assert_eq!(&parsed.to_string(), &s);
+
+            let ranges = RangeAlmanac::from2(&input).unwrap();
+            prop_assert_eq!(&ranges.to_string(), &s);
+            let parsed = RangeAlmanac::from2(&s).unwrap();
+            prop_assert_eq!(&parsed, &ranges);
+            prop_assert_eq!(parsed.to_string(), s);
+        }
+    }
 }
@@ -0,0 +1,78 @@
+//! Differential tests of part 2: small random almanacs are solved value by
+//! value, range by range and through the composed function, which must all
+//! agree. Rows may overlap or be empty, to reach the corners of range
+//! splitting. On a mismatch proptest shrinks the almanac and reports the
+//! smallest input that still disagrees.
+
+use proptest::prelude::*;
+
+use super::{Almanac, Kind, RangeAlmanac};
+
+const STAGES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// Seed ranges and the rows of every stage, as puzzle input.
+fn inputs() -> impl Strategy<Value = String> {
+    let seeds = prop::collection::vec((0..50u64, 0..10u64), 0..4);
+    let row = (0..60u64, 0..60u64, 0..15u64);
+    let stages = prop::collection::vec(prop::collection::vec(row, 0..4), 7);
+    (seeds, stages).prop_map(|(seeds, stages)| {
+        let mut s = String::from("seeds:");
+        for (start, len) in seeds {
+            s.push_str(&format!(" {} {}", start, len));
+        }
+        s.push('\n');
+        for (names, rows) in STAGES.windows(2).zip(stages) {
+            s.push_str(&format!("\n{}-to-{} map:\n", names[0], names[1]));
+            for (dest, source, len) in rows {
+                s.push_str(&format!("{} {} {}\n", dest, source, len));
+            }
+        }
+        s
+    })
+}
+
+fn by_value(s: &str) -> Option<u64> {
+    let mut almanac = Almanac::from2(s).unwrap();
+    almanac.process_until(&Kind::LOCATION).unwrap();
+    almanac.smallest_number()
+}
+
+fn by_range(s: &str) -> Option<u64> {
+    let mut almanac = RangeAlmanac::from2(s).unwrap();
+    almanac.process_until(&Kind::LOCATION).unwrap();
+    almanac.smallest_number()
+}
+
+fn composed(s: &str) -> Option<u64> {
+    let almanac = Almanac::from2(s).unwrap();
+    let location = almanac.compose("seed", "location").unwrap();
+    almanac.values.iter().map(|v| location.apply(v.value)).min()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1000))]
+
+    #[test]
+    fn range_matches_values(s in inputs()) {
+        let expected = by_value(&s);
+        prop_assert_eq!(by_range(&s), expected, "input:\n{}", s);
+        prop_assert_eq!(composed(&s), expected, "input:\n{}", s);
+    }
+}
+
+#[test]
+fn sample() {
+    let s = super::tests::TEST_INPUT;
+    assert_eq!(by_value(s), Some(46));
+    assert_eq!(by_range(s), Some(46));
+    assert_eq!(composed(s), Some(46));
+}
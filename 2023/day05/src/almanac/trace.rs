@@ -0,0 +1,131 @@
+use std::fmt;
+
+use super::{Almanac, AlmanacError, Kind};
+
+/// The categories a seed passes through, from [`Almanac::trace`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trace {
+    pub steps: Vec<Step>,
+}
+
+/// A value in one category, and the input line of the map row that produced
+/// it, `None` for the seed itself or a value passed through unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub kind: Kind,
+    pub value: u64,
+    pub line: Option<usize>,
+}
+
+impl Almanac {
+    /// Follows `seed` to its location, through the same stages as
+    /// [`Almanac::process_until`].
+    pub fn trace(&self, seed: u64) -> Result<Trace, AlmanacError> {
+        let mut steps = vec![Step {
+            kind: Kind::SEED,
+            value: seed,
+            line: None,
+        }];
+
+        for stage in self.sections.route(&Kind::SEED, &Kind::LOCATION)? {
+            let value = steps[steps.len() - 1].value;
+            let row = self.row(stage, value);
+            steps.push(Step {
+                kind: stage.1.clone(),
+                value: row.map_or(value, |m| m.map(value)),
+                line: row.map(|m| m.line),
+            });
+        }
+
+        Ok(Trace { steps })
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{} {}", step.kind, step.value)?;
+            match step.line {
+                Some(line) => write!(f, " (line {})", line)?,
+                None if i > 0 => write!(f, " (unchanged)")?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::tests::TEST_INPUT;
+
+    #[test]
+    fn trace() {
+        let almanac = Almanac::from1(TEST_INPUT).unwrap();
+        let trace = almanac.trace(79).unwrap();
+        assert_eq!(
+            trace.steps.iter().map(|s| s.value).collect::<Vec<_>>(),
+            [79, 81, 81, 81, 74, 78, 78, 82]
+        );
+        assert_eq!(
+            trace.steps.iter().map(|s| s.line).collect::<Vec<_>>(),
+            [
+                None,
+                Some(4),
+                None,
+                None,
+                Some(19),
+                Some(24),
+                None,
+                Some(31)
+            ]
+        );
+        assert_eq!(trace.steps.last().unwrap().kind, Kind::LOCATION);
+        assert_eq!(
+            trace.to_string(),
+            "seed 79 -> soil 81 (line 4) -> fertilizer 81 (unchanged) \
+             -> water 81 (unchanged) -> light 74 (line 19) -> temperature 78 (line 24) \
+             -> humidity 78 (unchanged) -> location 82 (line 31)"
+        );
+    }
+
+    #[test]
+    fn trace_second_branch() {
+        let s = "seeds: 1
+seed-to-rock map:
+
+seed-to-soil map:
+5 0 10
+
+soil-to-location map:";
+        let almanac = Almanac::from1(s).unwrap();
+        assert_eq!(
+            almanac.trace(1).unwrap().to_string(),
+            "seed 1 -> soil 6 (line 5) -> location 6 (unchanged)"
+        );
+    }
+
+    #[test]
+    fn trace_errors() {
+        let s = "seeds: 1
+seed-to-soil map:
+5 0 10
+
+soil-to-seed map:
+0 5 10
+
+location-to-seed map:";
+        let almanac = Almanac::from1(s).unwrap();
+        assert_eq!(almanac.trace(1), Err(AlmanacError::Cycle(Kind::SEED)));
+
+        let almanac = Almanac::from1("seeds: 1\nseed-to-soil map:").unwrap();
+        assert_eq!(
+            almanac.trace(1),
+            Err(AlmanacError::UnknownCategory(Kind::LOCATION))
+        );
+    }
+}
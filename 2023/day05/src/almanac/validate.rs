@@ -0,0 +1,190 @@
+use std::{fmt, ops::Range};
+
+use super::{Almanac, Kind, Map};
+
+/// A suspicious map row or stage, found by [`Almanac::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diagnostic {
+    /// Two rows of a stage map some of the same values, the earlier one wins.
+    Overlap {
+        from: Kind,
+        to: Kind,
+        lines: (usize, usize),
+        range: Range<u64>,
+    },
+    /// Values between the rows of a stage, which pass through unchanged.
+    Gap {
+        from: Kind,
+        to: Kind,
+        range: Range<u64>,
+    },
+    /// A row that maps nothing.
+    ZeroLength { line: usize },
+    /// A row whose source or destination range ends past `u64::MAX`, which
+    /// processing refuses to follow.
+    Overflow { line: usize },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overlap {
+                from,
+                to,
+                lines: (a, b),
+                range,
+            } => write!(
+                f,
+                "lines {} and {} of `{}-to-{}` both map {:?}",
+                a, b, from, to, range
+            ),
+            Self::Gap { from, to, range } => {
+                write!(f, "`{}-to-{}` passes {:?} through", from, to, range)
+            }
+            Self::ZeroLength { line } => write!(f, "line {}: row has zero length", line),
+            Self::Overflow { line } => write!(f, "line {}: row ends past u64::MAX", line),
+        }
+    }
+}
+
+impl Almanac {
+    /// Every diagnostic for the map rows, stage by stage in input order.
+    /// An empty list means no stage has overlapping rows or holes between
+    /// them, and every row fits in `u64`.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for stage in &self.sections.stages {
+            let (from, to) = stage;
+            let mut rows = Vec::new();
+            for m in self.sections.rows(stage) {
+                if m.length == 0 {
+                    diagnostics.push(Diagnostic::ZeroLength { line: m.line });
+                } else if !m.fits() {
+                    diagnostics.push(Diagnostic::Overflow { line: m.line });
+                } else {
+                    rows.push(m);
+                }
+            }
+
+            for (i, a) in rows.iter().enumerate() {
+                for b in &rows[i + 1..] {
+                    let start = a.source_start.max(b.source_start);
+                    let end = a.source_range().end.min(b.source_range().end);
+                    if start < end {
+                        diagnostics.push(Diagnostic::Overlap {
+                            from: from.clone(),
+                            to: to.clone(),
+                            lines: (a.line, b.line),
+                            range: start..end,
+                        });
+                    }
+                }
+            }
+
+            rows.sort_by_key(|m| m.source_start);
+            let mut end = None;
+            for m in rows {
+                let source = m.source_range();
+                if let Some(end) = end.filter(|end| *end < source.start) {
+                    diagnostics.push(Diagnostic::Gap {
+                        from: from.clone(),
+                        to: to.clone(),
+                        range: end..source.start,
+                    });
+                }
+                end = Some(end.map_or(source.end, |end: u64| end.max(source.end)));
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Parts of `range` that no map out of `from` covers, so they pass
+    /// through to the next category unchanged.
+    pub fn uncovered(&self, from: &str, range: Range<u64>) -> Vec<Range<u64>> {
+        let from = Kind::new(from);
+        let mut rows: Vec<Range<u64>> = self
+            .sections
+            .maps
+            .iter()
+            .filter(|m| m.from == from && m.fits())
+            .map(Map::source_range)
+            .collect();
+        rows.sort_by_key(|r| r.start);
+
+        let mut uncovered = Vec::new();
+        let mut start = range.start;
+        for r in rows {
+            if r.start > start {
+                uncovered.push(start..r.start.min(range.end));
+            }
+            start = start.max(r.end);
+            if start >= range.end {
+                break;
+            }
+        }
+        if start < range.end {
+            uncovered.push(start..range.end);
+        }
+        uncovered.retain(|r| !r.is_empty());
+        uncovered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::almanac::tests::TEST_INPUT;
+
+    #[test]
+    fn sample_is_valid() {
+        let almanac = Almanac::from1(TEST_INPUT).unwrap();
+        assert_eq!(almanac.validate(), vec![]);
+    }
+
+    #[test]
+    fn validate() {
+        let s = "seeds: 1
+seed-to-soil map:
+10 0 5
+20 3 5
+0 20 0
+30 40 5
+1 18446744073709551615 2";
+        let almanac = Almanac::from1(s).unwrap();
+        let seed = Kind::SEED;
+        let soil = Kind::new("soil");
+        assert_eq!(
+            almanac.validate(),
+            vec![
+                Diagnostic::ZeroLength { line: 5 },
+                Diagnostic::Overflow { line: 7 },
+                Diagnostic::Overlap {
+                    from: seed.clone(),
+                    to: soil.clone(),
+                    lines: (3, 4),
+                    range: 3..5,
+                },
+                Diagnostic::Gap {
+                    from: seed,
+                    to: soil,
+                    range: 8..40,
+                },
+            ]
+        );
+        assert_eq!(
+            almanac.validate()[2].to_string(),
+            "lines 3 and 4 of `seed-to-soil` both map 3..5"
+        );
+    }
+
+    #[test]
+    fn uncovered() {
+        let almanac = Almanac::from1(TEST_INPUT).unwrap();
+        assert_eq!(almanac.uncovered("seed", 79..93), vec![]);
+        assert_eq!(almanac.uncovered("seed", 40..60), vec![40..50]);
+        assert_eq!(almanac.uncovered("soil", 0..100), vec![54..100]);
+        assert_eq!(almanac.uncovered("location", 5..6), vec![5..6]);
+    }
+}
@@ -0,0 +1,256 @@
+use std::{collections::BTreeSet, io};
+
+/// The categories of the puzzle, in order.
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// Generates random almanacs in the puzzle's format, the same ones for
+/// the same seed.
+///
+/// Unless [`Generator::overlapping`] is set, the rows of a stage never
+/// share source or destination values, like in real puzzle inputs.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    seed: u64,
+    seed_ranges: usize,
+    seed_range_len: u64,
+    rows: usize,
+    max_value: u64,
+    overlapping: bool,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            seed_ranges: 10,
+            seed_range_len: 1 << 28,
+            rows: 30,
+            max_value: 1 << 32,
+            overlapping: false,
+        }
+    }
+
+    /// Number of `<start> <length>` pairs on the seeds line.
+    pub fn seed_ranges(mut self, seed_ranges: usize) -> Self {
+        self.seed_ranges = seed_ranges;
+        self
+    }
+
+    /// Largest length of a seed range.
+    pub fn seed_range_len(mut self, seed_range_len: u64) -> Self {
+        self.seed_range_len = seed_range_len.max(1);
+        self
+    }
+
+    /// Rows in each of the seven map sections.
+    pub fn rows(mut self, rows: usize) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Every seed and map range lies below this.
+    pub fn max_value(mut self, max_value: u64) -> Self {
+        self.max_value = max_value.max(1);
+        self
+    }
+
+    /// Draws every row independently, so rows may overlap.
+    pub fn overlapping(mut self, overlapping: bool) -> Self {
+        self.overlapping = overlapping;
+        self
+    }
+
+    pub fn generate(&self) -> String {
+        let mut out = Vec::new();
+        self.write(&mut out).expect("writing to a Vec never fails");
+        String::from_utf8(out).expect("almanacs are ASCII")
+    }
+
+    pub fn write(&self, out: &mut impl io::Write) -> io::Result<()> {
+        let mut rng = SplitMix64(self.seed);
+
+        write!(out, "seeds:")?;
+        for _ in 0..self.seed_ranges {
+            let len = 1 + rng.below(self.seed_range_len.min(self.max_value));
+            let start = rng.below(self.max_value - len + 1);
+            write!(out, " {} {}", start, len)?;
+        }
+        writeln!(out)?;
+
+        for stage in CATEGORIES.windows(2) {
+            write!(out, "\n{}-to-{} map:\n", stage[0], stage[1])?;
+            let rows = match self.overlapping {
+                true => self.overlapping_rows(&mut rng),
+                false => self.disjoint_rows(&mut rng),
+            };
+            for (dest, source, len) in rows {
+                writeln!(out, "{} {} {}", dest, source, len)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rows with disjoint source ranges, laid out again in a shuffled
+    /// order with random gaps to get disjoint destinations.
+    fn disjoint_rows(&self, rng: &mut SplitMix64) -> Vec<(u64, u64, u64)> {
+        let rows = self.rows.min((self.max_value / 2) as usize);
+        let mut bounds = BTreeSet::new();
+        while bounds.len() < 2 * rows {
+            bounds.insert(rng.below(self.max_value));
+        }
+        let bounds: Vec<u64> = bounds.into_iter().collect();
+        let sources: Vec<(u64, u64)> = bounds
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1] - pair[0]))
+            .collect();
+
+        let mut order: Vec<usize> = (0..sources.len()).collect();
+        rng.shuffle(&mut order);
+        let free = self.max_value - sources.iter().map(|(_, len)| len).sum::<u64>();
+        // How much of the free space lies before each destination range.
+        let mut before: Vec<u64> = (0..sources.len())
+            .map(|_| rng.below(free.saturating_add(1)))
+            .collect();
+        before.sort_unstable();
+
+        let mut dests = vec![0; sources.len()];
+        let mut taken = 0;
+        for (&i, before) in order.iter().zip(before) {
+            dests[i] = before + taken;
+            taken += sources[i].1;
+        }
+
+        let mut rows: Vec<(u64, u64, u64)> = sources
+            .into_iter()
+            .zip(dests)
+            .map(|((source, len), dest)| (dest, source, len))
+            .collect();
+        rng.shuffle(&mut rows);
+        rows
+    }
+
+    fn overlapping_rows(&self, rng: &mut SplitMix64) -> Vec<(u64, u64, u64)> {
+        (0..self.rows)
+            .map(|_| {
+                let len = 1 + rng.below(self.max_value / 4 + 1);
+                let len = len.min(self.max_value);
+                let source = rng.below(self.max_value - len + 1);
+                let dest = rng.below(self.max_value - len + 1);
+                (dest, source, len)
+            })
+            .collect()
+    }
+}
+
+/// A small, fast generator whose output never changes between releases.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..n`, or 0 when `n` is 0.
+    fn below(&mut self, n: u64) -> u64 {
+        match n {
+            0 => 0,
+            n => self.next() % n,
+        }
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Almanac, Diagnostic, Kind, RangeAlmanac};
+
+    #[test]
+    fn reproducible() {
+        let generator = Generator::new(7).rows(5).max_value(1000);
+        assert_eq!(generator.generate(), generator.generate());
+        assert_ne!(generator.generate(), Generator::new(8).generate());
+    }
+
+    #[test]
+    fn parses_without_overlaps() {
+        for seed in 0..20 {
+            let s = Generator::new(seed)
+                .seed_ranges(3)
+                .seed_range_len(50)
+                .rows(8)
+                .max_value(500)
+                .generate();
+            let almanac = Almanac::from1(&s).unwrap();
+            let diagnostics = almanac.validate();
+            assert!(
+                diagnostics
+                    .iter()
+                    .all(|d| matches!(d, Diagnostic::Gap { .. })),
+                "{:?}\n{}",
+                diagnostics,
+                s
+            );
+
+            for section in s.split("\n\n").skip(1) {
+                let mut dests: Vec<(u64, u64)> = section
+                    .lines()
+                    .skip(1)
+                    .map(|row| {
+                        let row: Vec<u64> = row.split(' ').map(|n| n.parse().unwrap()).collect();
+                        (row[0], row[0] + row[2])
+                    })
+                    .collect();
+                dests.sort_unstable();
+                assert!(dests.windows(2).all(|w| w[0].1 <= w[1].0), "{}", section);
+            }
+
+            let mut ranges = RangeAlmanac::from2(&s).unwrap();
+            ranges.process_until(&Kind::LOCATION).unwrap();
+            assert!(ranges.smallest_number().is_some());
+        }
+    }
+
+    #[test]
+    fn overlapping() {
+        let s = Generator::new(1)
+            .rows(20)
+            .max_value(100)
+            .overlapping(true)
+            .generate();
+        let almanac = Almanac::from1(&s).unwrap();
+        assert!(almanac
+            .validate()
+            .iter()
+            .any(|d| matches!(d, Diagnostic::Overlap { .. })));
+    }
+
+    #[test]
+    fn full_range() {
+        let s = Generator::new(3).max_value(u64::MAX).generate();
+        let almanac = Almanac::from1(&s).unwrap();
+        assert!(almanac
+            .validate()
+            .iter()
+            .all(|d| matches!(d, Diagnostic::Gap { .. })));
+    }
+}
@@ -0,0 +1,52 @@
+use aoc_core::{Day, Error, Result, Solution};
+
+pub use almanac::{Almanac, AlmanacError, Diagnostic, Kind, RangeAlmanac, Step, Trace};
+pub use generate::Generator;
+pub use piecewise::Piecewise;
+
+mod almanac;
+mod generate;
+mod piecewise;
+
+pub const DAY: Day = Day::new::<Day05>();
+
+pub struct Day05 {
+    almanac: Almanac,
+    ranges: RangeAlmanac,
+}
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self {
+            almanac: Almanac::from1(input)?,
+            ranges: RangeAlmanac::from2(input)?,
+        })
+    }
+
+    fn part1(&self) -> Result<u64> {
+        let mut almanac = self.almanac.clone();
+        almanac
+            .process_until(&Kind::LOCATION)
+            .map_err(|e| Error::Solve {
+                part: 1,
+                source: e.into(),
+            })?;
+        almanac.smallest_number().ok_or(Error::NoAnswer(1))
+    }
+
+    fn part2(&self) -> Result<u64> {
+        let mut ranges = self.ranges.clone();
+        ranges
+            .process_until(&Kind::LOCATION)
+            .map_err(|e| Error::Solve {
+                part: 2,
+                source: e.into(),
+            })?;
+        ranges.smallest_number().ok_or(Error::NoAnswer(2))
+    }
+}
@@ -1,18 +1,31 @@
-use std::fs::read_to_string;
-
-mod almanac;
+use aoc_core::{input::Source, Error, Solution};
+use day05::{Almanac, Day05};
 
+/// `day05 [INPUT | -] [--trace SEED...]`, printing how each seed reaches
+/// its location instead of the answers when `--trace` is given.
 fn main() -> anyhow::Result<()> {
-    let s = read_to_string("input.txt")?;
-
-    let mut almanac = almanac::Almanac::from1(&s)?;
-    almanac.process_times(10);
-    println!(
-        "Smallest location number: {}",
-        almanac.smallest_number().unwrap()
-    );
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(i) = args.iter().position(|arg| arg == "--trace") else {
+        aoc_core::run::<Day05>()?;
+        return Ok(());
+    };
 
-    println!("Part 2 is still horribly optimized, it crashes!");
+    let (input, seeds) = (&args[..i], &args[i + 1..]);
+    if input.len() > 1 || seeds.is_empty() {
+        return Err(Error::Usage("usage: day05 [INPUT | -] [--trace SEED...]".to_string()).into());
+    }
+    let seeds = seeds
+        .iter()
+        .map(|seed| {
+            seed.parse::<u64>()
+                .map_err(|_| Error::Usage(format!("seed `{}` is not a number", seed)))
+        })
+        .collect::<Result<Vec<u64>, Error>>()?;
 
+    let source = Source::resolve(Day05::DAY, input.first().map(String::as_str), "input.txt");
+    let almanac = Almanac::from1(&source.read(Day05::DAY)?)?;
+    for seed in seeds {
+        println!("{}", almanac.trace(seed)?);
+    }
     Ok(())
 }
@@ -0,0 +1,171 @@
+use std::ops::Range;
+
+/// A piecewise-linear function over every `u64`, made of sorted,
+/// contiguous segments that each shift their values by a fixed offset.
+///
+/// One almanac stage is such a function, and so is any chain of them,
+/// which lets seed to location be looked up with a single binary search.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Piecewise {
+    /// Sorted by `start`, the first starting at 0. Each segment runs up to
+    /// the start of the next one, the last one up to `u64::MAX`.
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Segment {
+    start: u64,
+    dest: u64,
+}
+
+impl Piecewise {
+    pub fn identity() -> Self {
+        Self {
+            segments: vec![Segment { start: 0, dest: 0 }],
+        }
+    }
+
+    /// One stage from its map rows, as source range and destination start.
+    /// Where rows overlap the earlier one wins, values outside every row
+    /// pass through unchanged. Destination ranges must end within `u64`,
+    /// as the almanac checks before composing.
+    pub(crate) fn stage(rows: &[(Range<u64>, u64)]) -> Self {
+        let mut starts: Vec<u64> = rows
+            .iter()
+            .flat_map(|(source, _)| [source.start, source.end])
+            .chain([0])
+            .collect();
+        starts.sort_unstable();
+        starts.dedup();
+
+        let segments = starts
+            .into_iter()
+            .map(|start| {
+                let dest = rows
+                    .iter()
+                    .find(|(source, _)| source.contains(&start))
+                    .map_or(start, |(source, dest)| start - source.start + dest);
+                Segment { start, dest }
+            })
+            .collect();
+        Self::merged(segments)
+    }
+
+    /// Applies `self`, then `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        let mut starts = Vec::new();
+        for (i, segment) in self.segments.iter().enumerate() {
+            starts.push(segment.start);
+            let len = self.len(i);
+            let image = segment.dest as u128..segment.dest as u128 + len;
+            starts.extend(
+                next.segments
+                    .iter()
+                    .map(|s| s.start as u128)
+                    .filter(|s| image.start < *s && *s < image.end)
+                    .map(|s| (segment.start as u128 + s - image.start) as u64),
+            );
+        }
+
+        let segments = starts
+            .into_iter()
+            .map(|start| Segment {
+                start,
+                dest: next.apply(self.apply(start)),
+            })
+            .collect();
+        Self::merged(segments)
+    }
+
+    /// The value `x` maps to, in O(log n) for n segments.
+    pub fn apply(&self, x: u64) -> u64 {
+        let i = self.segments.partition_point(|s| s.start <= x) - 1;
+        let segment = self.segments[i];
+        x - segment.start + segment.dest
+    }
+
+    /// The function mapping every output back to its input, if each output
+    /// comes from exactly one input.
+    pub fn inverse(&self) -> Option<Self> {
+        let mut images: Vec<(Segment, u128)> = (0..self.segments.len())
+            .map(|i| {
+                let s = self.segments[i];
+                let inverted = Segment {
+                    start: s.dest,
+                    dest: s.start,
+                };
+                (inverted, self.len(i))
+            })
+            .collect();
+        images.sort_unstable_by_key(|(s, _)| s.start);
+
+        let mut end = 0u128;
+        for (s, len) in &images {
+            if s.start as u128 != end {
+                return None;
+            }
+            end += len;
+        }
+        (end == 1 << 64).then(|| Self::merged(images.into_iter().map(|(s, _)| s).collect()))
+    }
+
+    /// Number of values covered by the `i`th segment.
+    fn len(&self, i: usize) -> u128 {
+        let end = self
+            .segments
+            .get(i + 1)
+            .map_or(1 << 64, |next| next.start as u128);
+        end - self.segments[i].start as u128
+    }
+
+    /// Drops segments that just continue the previous one's offset.
+    fn merged(segments: Vec<Segment>) -> Self {
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for s in segments {
+            let continues = merged.last().is_some_and(|last| {
+                s.start.wrapping_sub(last.start) == s.dest.wrapping_sub(last.dest)
+            });
+            if !continues {
+                merged.push(s);
+            }
+        }
+        Self { segments: merged }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage() {
+        let f = Piecewise::stage(&[(98..100, 50), (50..98, 52)]);
+        assert_eq!(
+            [0, 49, 50, 51, 97, 98, 99, 100].map(|x| f.apply(x)),
+            [0, 49, 52, 53, 99, 50, 51, 100]
+        );
+        assert_eq!(f.apply(u64::MAX), u64::MAX);
+
+        let overlapping = Piecewise::stage(&[(0..10, 100), (5..15, 200)]);
+        assert_eq!(
+            [4, 9, 10, 14].map(|x| overlapping.apply(x)),
+            [104, 109, 205, 209]
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        let f = Piecewise::stage(&[(98..100, 50), (50..98, 52)]);
+        let g = f.inverse().unwrap();
+        for x in 0..200 {
+            assert_eq!(g.apply(f.apply(x)), x);
+        }
+        assert_eq!(Piecewise::identity().inverse(), Some(Piecewise::identity()));
+
+        // 0..10 and 10..20 both land on 100..110.
+        assert_eq!(
+            Piecewise::stage(&[(0..10, 100), (10..20, 100)]).inverse(),
+            None
+        );
+    }
+}